@@ -43,20 +43,18 @@ var<uniform> sky: SkyUniform;
 
 var<immediate> imm: Immediates;
 
+fn unpack(packed: u32) -> vec3<f32> {
+    return vec3(f32(packed % 17u), f32(packed / 17u % 17u), f32(packed / 289u));
+}
+
 @vertex
 fn vs_main(vertex: VertexInput) -> VertexOutput {
-    let coords = imm.chunk_coords * 16.0 + vec3(
-        f32(extractBits(vertex.data[0], 0u, 5u)),
-        f32(extractBits(vertex.data[0], 5u, 5u)),
-        f32(extractBits(vertex.data[0], 10u, 5u)),
-    );
-    let tex_idx = extractBits(vertex.data[0], 15u, 8u);
-    let tex_coords = vec2(
-        f32(extractBits(vertex.data[0], 27u, 5u)),
-        f32(extractBits(vertex.data[1], 27u, 5u)),
-    );
-    let face = extractBits(vertex.data[0], 23u, 2u);
-    let ao = f32(extractBits(vertex.data[0], 25u, 2u));
+    let coords = imm.chunk_coords * 16.0 + unpack(extractBits(vertex.data[0], 0u, 13u))
+        - vec3(0.0, f32(extractBits(vertex.data[1], 28u, 4u)) / 16.0, 0.0);
+    let tex_idx = extractBits(vertex.data[0], 22u, 10u);
+    let tex_coords = unpack(extractBits(vertex.data[0], 13u, 9u)).xy;
+    let face = extractBits(vertex.data[1], 24u, 2u);
+    let ao = f32(extractBits(vertex.data[1], 26u, 2u));
     let skylight = vec3(
         f32(extractBits(vertex.data[1], 0u, 4u)),
         f32(extractBits(vertex.data[1], 4u, 4u)),
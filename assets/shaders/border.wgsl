@@ -0,0 +1,45 @@
+struct VertexInput {
+    @location(0) coords: vec3<f32>,
+}
+
+struct PlayerUniform {
+    vp: mat4x4<f32>,
+    inv_vp: mat4x4<f32>,
+    origin: vec3<f32>,
+    forward: vec3<f32>,
+    render_distance: u32,
+    znear: f32,
+    zfar: f32,
+}
+
+struct Immediates {
+    color: vec3<f32>,
+    opacity: f32,
+    fade_distance: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) coords: vec3<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> player: PlayerUniform;
+
+var<immediate> imm: Immediates;
+
+@vertex
+fn vs_main(vertex: VertexInput) -> VertexOutput {
+    return VertexOutput(
+        player.vp * vec4(-player.origin + vertex.coords, 1.0),
+        vertex.coords,
+    );
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.coords - player.origin);
+    let fade = 1.0 - smoothstep(0.0, imm.fade_distance, distance);
+    let stripe = step(0.5, fract((in.coords.x + in.coords.y + in.coords.z) * 0.25));
+    return vec4(imm.color, imm.opacity * fade * mix(0.6, 1.0, stripe));
+}
@@ -35,14 +35,14 @@ var<uniform> player: PlayerUniform;
 
 var<immediate> imm: Immediates;
 
+fn unpack(packed: u32) -> vec3<f32> {
+    return vec3(f32(packed % 17u), f32(packed / 17u % 17u), f32(packed / 289u));
+}
+
 @vertex
 fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
-    let coords = vec3(
-        f32(extractBits(vertex.data[0], 0u, 5u)),
-        f32(extractBits(vertex.data[0], 5u, 5u)),
-        f32(extractBits(vertex.data[0], 10u, 5u)),
-    );
-    let face = extractBits(vertex.data[0], 23u, 2u);
+    let coords = unpack(extractBits(vertex.data[0], 0u, 13u));
+    let face = extractBits(vertex.data[1], 24u, 2u);
     let offset = instance.offset - rem_euclid(player.origin.xz - imm.offset, imm.size.x);
     let light_factor = mix(mix(mix(mix(0.0, 0.6, f32(face == 0u)), 1.0, f32(face == 1u)), 0.5, f32(face == 2u)), 0.8, f32(face == 3u));
     return VertexOutput(
@@ -0,0 +1,79 @@
+enable wgpu_binding_array;
+
+struct VertexInput {
+    @location(0) data: vec2<u32>,
+}
+
+struct PlayerUniform {
+    vp: mat4x4<f32>,
+    inv_vp: mat4x4<f32>,
+    origin: vec3<f32>,
+    forward: vec3<f32>,
+    render_distance: u32,
+    znear: f32,
+    zfar: f32,
+}
+
+struct SkyUniform {
+    sun_dir: vec3<f32>,
+    color: vec3<f32>,
+    horizon_color: vec3<f32>,
+    glow_color: vec4<f32>,
+    glow_angle: f32,
+    sun_intensity: f32,
+    light_intensity: vec3<f32>,
+}
+
+struct Immediates {
+    m: mat4x4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) @interpolate(flat) tex_index: u32,
+    @location(1) tex_coords: vec2<f32>,
+    @location(2) light_factor: vec3<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> player: PlayerUniform;
+
+@group(1) @binding(0)
+var<uniform> sky: SkyUniform;
+
+var<immediate> imm: Immediates;
+
+fn unpack(packed: u32) -> vec3<f32> {
+    return vec3(f32(packed % 17u), f32(packed / 17u % 17u), f32(packed / 289u));
+}
+
+@vertex
+fn vs_main(vertex: VertexInput) -> VertexOutput {
+    let coords = imm.m * vec4(unpack(extractBits(vertex.data[0], 0u, 13u)), 1.0);
+    let tex_idx = extractBits(vertex.data[0], 22u, 10u);
+    let tex_coords = unpack(extractBits(vertex.data[0], 13u, 9u)).xy;
+    let face = extractBits(vertex.data[1], 24u, 2u);
+    let face_light = mix(mix(mix(mix(0.0, 0.6, f32(face == 0u)), 1.0, f32(face == 1u)), 0.5, f32(face == 2u)), 0.8, f32(face == 3u));
+    return VertexOutput(
+        player.vp * vec4(-player.origin + coords.xyz, 1.0),
+        tex_idx,
+        tex_coords,
+        saturate(sky.light_intensity + 0.2) * face_light,
+    );
+}
+
+@group(2) @binding(0)
+var t_blocks: binding_array<texture_2d<f32>>;
+
+@group(2) @binding(1)
+var s_block: sampler;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(t_blocks[in.tex_index], s_block, in.tex_coords);
+    if color.a == 0.0 {
+        discard;
+    } else {
+        return color * vec4(in.light_factor, 1.0);
+    }
+}
@@ -18,20 +18,16 @@ struct VertexOutput {
 @group(0) @binding(0)
 var<uniform> inventory: InventoryUniform;
 
+fn unpack(packed: u32) -> vec3<f32> {
+    return vec3(f32(packed % 17u), f32(packed / 17u % 17u), f32(packed / 289u));
+}
+
 @vertex
 fn vs_main(vertex: VertexInput) -> VertexOutput {
-    let coords = inventory.transform * vec4(
-        f32(extractBits(vertex.data[0], 0u, 5u)),
-        f32(extractBits(vertex.data[0], 5u, 5u)),
-        f32(extractBits(vertex.data[0], 10u, 5u)),
-        1.0,
-    );
-    let tex_idx = extractBits(vertex.data[0], 15u, 8u);
-    let tex_coords = vec2(
-        f32(extractBits(vertex.data[0], 27u, 5u)),
-        f32(extractBits(vertex.data[1], 27u, 5u)),
-    );
-    let face = extractBits(vertex.data[0], 23u, 2u);
+    let coords = inventory.transform * vec4(unpack(extractBits(vertex.data[0], 0u, 13u)), 1.0);
+    let tex_idx = extractBits(vertex.data[0], 22u, 10u);
+    let tex_coords = unpack(extractBits(vertex.data[0], 13u, 9u)).xy;
+    let face = extractBits(vertex.data[1], 24u, 2u);
     let face_light = mix(mix(mix(mix(0.0, 0.6, f32(face == 0u)), 1.0, f32(face == 1u)), 0.5, f32(face == 2u)), 0.8, f32(face == 3u));
     return VertexOutput(coords, tex_idx, tex_coords, face_light);
 }
@@ -17,6 +17,7 @@ struct PlayerUniform {
 struct Immediates {
     m: mat4x4<f32>,
     tex_index: u32,
+    phase: f32,
 }
 
 struct VertexOutput {
@@ -59,5 +60,15 @@ var s_object: sampler;
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let color = textureSample(t_object[imm.tex_index], s_object, in.tex_coords);
-    return color * vec4(vec3(sky.sun_intensity), 1.0);
+    return color * vec4(vec3(sky.sun_intensity * lit(in.tex_coords)), 1.0);
+}
+
+fn lit(tex_coords: vec2<f32>) -> f32 {
+    let coords = tex_coords * 2.0 - 1.0;
+    let terminator = cos(6.2831855 * imm.phase) * sqrt(max(1.0 - coords.y * coords.y, 0.0));
+    if imm.phase < 0.5 {
+        return f32(coords.x > terminator);
+    } else {
+        return f32(coords.x < -terminator);
+    }
 }
@@ -0,0 +1,35 @@
+struct VertexInput {
+    @location(0) coords: vec2<f32>,
+    @location(1) tex_coords: vec2<f32>,
+    @location(2) color: vec4<f32>,
+}
+
+struct TextUniform {
+    transform: mat4x4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> text: TextUniform;
+
+@vertex
+fn vs_main(vertex: VertexInput) -> VertexOutput {
+    let coords = text.transform * vec4(vertex.coords, 0.0, 1.0);
+    return VertexOutput(coords, vertex.tex_coords, vertex.color);
+}
+
+@group(1) @binding(0)
+var t_font: texture_2d<f32>;
+
+@group(1) @binding(1)
+var s_font: sampler;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color * textureSample(t_font, s_font, in.tex_coords);
+}
@@ -0,0 +1,67 @@
+struct VertexInput {
+    @builtin(vertex_index) index: u32,
+}
+
+struct InstanceInput {
+    @location(0) origin: vec3<f32>,
+    @location(1) opacity: f32,
+    @location(2) color: vec3<f32>,
+    @location(3) size: vec2<f32>,
+}
+
+struct PlayerUniform {
+    vp: mat4x4<f32>,
+    inv_vp: mat4x4<f32>,
+    origin: vec3<f32>,
+    forward: vec3<f32>,
+    render_distance: u32,
+    znear: f32,
+    zfar: f32,
+}
+
+struct SkyUniform {
+    sun_dir: vec3<f32>,
+    color: vec3<f32>,
+    horizon_color: vec3<f32>,
+    glow_color: vec4<f32>,
+    glow_angle: f32,
+    sun_intensity: f32,
+    light_intensity: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> player: PlayerUniform;
+
+@group(1) @binding(0)
+var<uniform> sky: SkyUniform;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let x = f32(((vertex.index + 2u) / 3u) % 2u) - 0.5;
+    let y = f32(((vertex.index + 1u) / 3u) % 2u) - 0.5;
+    let horizontal = vec2(-player.forward.z, player.forward.x);
+    var right = vec3(1.0, 0.0, 0.0);
+    if length(horizontal) > 0.001 {
+        right = vec3(normalize(horizontal).x, 0.0, normalize(horizontal).y);
+    }
+    let coords = instance.origin
+        + right * x * instance.size.x
+        + vec3(0.0, y * instance.size.y, 0.0);
+    return VertexOutput(
+        player.vp * vec4(-player.origin + coords, 1.0),
+        vec2(x, y) * 2.0,
+        vec4(instance.color * saturate(sky.light_intensity + 0.2), instance.opacity),
+    );
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let falloff = 1.0 - smoothstep(0.5, 1.0, length(in.coords));
+    return vec4(in.color.rgb, in.color.a * falloff);
+}
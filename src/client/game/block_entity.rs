@@ -0,0 +1,51 @@
+use super::gui::Gui;
+use crate::{
+    client::event_loop::{Event, EventHandler},
+    server::{ServerEvent, game::world::block_entity::BlockEntity},
+    shared::utils,
+};
+use nalgebra::Point3;
+use rustc_hash::FxHashMap;
+
+#[derive(Default)]
+pub struct BlockEntities {
+    entities: FxHashMap<Point3<i64>, BlockEntity>,
+    hovered: Option<Point3<i64>>,
+}
+
+impl BlockEntities {
+    fn show_hovered(&self, gui: &mut Gui) {
+        let entity = self.hovered.and_then(|coords| self.entities.get(&coords));
+        gui.show_tooltip(entity.map(ToString::to_string));
+    }
+}
+
+impl EventHandler for BlockEntities {
+    type Context<'a> = &'a mut Gui;
+
+    fn handle(&mut self, event: &Event, gui: Self::Context<'_>) {
+        match event {
+            Event::ServerEvent(ServerEvent::BlockEntityUpdated { coords, entity }) => {
+                match entity {
+                    Some(entity) => self.entities.insert(*coords, entity.clone()),
+                    None => self.entities.remove(coords),
+                };
+                self.show_hovered(gui);
+            }
+            &Event::ServerEvent(ServerEvent::ChunkUnloaded { coords, .. }) => {
+                self.entities
+                    .retain(|&entity_coords, _| utils::chunk_coords(entity_coords) != coords);
+            }
+            Event::ServerEvent(ServerEvent::BlockHovered(data)) => {
+                self.hovered = data.map(|data| data.coords);
+                self.show_hovered(gui);
+            }
+            Event::ServerEvent(ServerEvent::WorldInitialized { .. }) => {
+                self.entities.clear();
+                self.hovered = None;
+                self.show_hovered(gui);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    client::{
+        CLIENT_CONFIG,
+        event_loop::{Event, EventHandler},
+        renderer::{
+            Renderer,
+            buffer::{MemoryState, VertexBuffer},
+            effect::PostProcessor,
+            program::Program,
+            texture::screen::DepthBuffer,
+            utils::{Immediates, Vertex, read_wgsl},
+        },
+    },
+    server::{
+        ServerEvent,
+        game::world::{border::WorldBorder, chunk::Chunk},
+    },
+    shared::color::{Rgb, Rgba},
+};
+use bytemuck::{Pod, Zeroable};
+use nalgebra::{Point2, Point3, point};
+use serde::Deserialize;
+use std::ops::Range;
+
+pub struct BorderWall {
+    vertex_buffer: Option<VertexBuffer<BorderVertex>>,
+    program: Program,
+}
+
+impl BorderWall {
+    pub fn new(renderer: &Renderer, player_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
+        let program = Program::builder()
+            .renderer(renderer)
+            .shader_desc(read_wgsl("assets/shaders/border.wgsl"))
+            .bind_group_layouts(&[player_bind_group_layout])
+            .immediate_size(BorderImmediates::SIZE)
+            .buffers(&[BorderVertex::desc()])
+            .depth_stencil(wgpu::DepthStencilState {
+                format: DepthBuffer::FORMAT,
+                depth_write_enabled: Some(false),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: Default::default(),
+                bias: Default::default(),
+            })
+            .format(PostProcessor::FORMAT)
+            .blend(wgpu::BlendState::ALPHA_BLENDING)
+            .build();
+        Self {
+            vertex_buffer: None,
+            program,
+        }
+    }
+
+    pub fn draw(
+        &self,
+        view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+        player_bind_group: &wgpu::BindGroup,
+        depth_view: &wgpu::TextureView,
+    ) {
+        let Some(buffer) = &self.vertex_buffer else {
+            return;
+        };
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+
+        self.program.bind(&mut render_pass, [player_bind_group]);
+        BorderImmediates::new().set(&mut render_pass);
+        buffer.draw(&mut render_pass);
+    }
+
+    fn vertices(border: WorldBorder, y_range: Range<i32>) -> Vec<BorderVertex> {
+        let (min, max) = (border.min().cast::<f32>(), border.max().cast::<f32>());
+        let bottom = (y_range.start as i64 * Chunk::DIM as i64) as f32;
+        let top = (y_range.end as i64 * Chunk::DIM as i64) as f32;
+        let corners = [
+            point![min.x, min.y],
+            point![max.x, min.y],
+            point![max.x, max.y],
+            point![min.x, max.y],
+        ];
+
+        (0..corners.len())
+            .flat_map(|i| {
+                let a = corners[i];
+                let b = corners[(i + 1) % corners.len()];
+                let vertex = |xz: Point2<f32>, y| BorderVertex {
+                    coords: point![xz.x, y, xz.y],
+                };
+                [
+                    vertex(a, bottom),
+                    vertex(b, bottom),
+                    vertex(b, top),
+                    vertex(b, top),
+                    vertex(a, top),
+                    vertex(a, bottom),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl EventHandler for BorderWall {
+    type Context<'a> = &'a Renderer;
+
+    fn handle(&mut self, event: &Event, renderer: Self::Context<'_>) {
+        if let Event::ServerEvent(ServerEvent::WorldInitialized {
+            y_range, border, ..
+        }) = event
+        {
+            self.vertex_buffer = VertexBuffer::try_new(
+                renderer,
+                MemoryState::Immutable(&Self::vertices(*border, y_range.clone())),
+            );
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct BorderVertex {
+    coords: Point3<f32>,
+}
+
+impl Vertex for BorderVertex {
+    const ATTRIBS: &[wgpu::VertexAttribute] = &wgpu::vertex_attr_array![0 => Float32x3];
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct BorderImmediates {
+    color: Rgb<f32>,
+    opacity: f32,
+    fade_distance: f32,
+    padding: [f32; 3],
+}
+
+impl BorderImmediates {
+    fn new() -> Self {
+        let BorderConfig {
+            color: Rgba { rgb, a },
+            fade_distance,
+        } = CLIENT_CONFIG.border;
+        Self {
+            color: rgb,
+            opacity: a,
+            fade_distance,
+            padding: Default::default(),
+        }
+    }
+}
+
+impl Immediates for BorderImmediates {}
+
+#[derive(Deserialize)]
+pub struct BorderConfig {
+    color: Rgba<f32>,
+    fade_distance: f32,
+}
@@ -0,0 +1,33 @@
+use crate::{
+    client::event_loop::{Event, EventHandler},
+    server::{ServerEvent, game::clock::Time},
+};
+use std::time::Duration;
+use winit::event::WindowEvent;
+
+#[derive(Default)]
+pub struct Clock {
+    time: Time,
+}
+
+impl Clock {
+    pub fn time(&self) -> Time {
+        self.time
+    }
+}
+
+impl EventHandler for Clock {
+    type Context<'a> = Duration;
+
+    fn handle(&mut self, event: &Event, dt: Self::Context<'_>) {
+        match *event {
+            Event::ServerEvent(ServerEvent::TimeUpdated(time)) => {
+                self.time = time;
+            }
+            Event::WindowEvent(WindowEvent::RedrawRequested) => {
+                self.time = self.time.advanced(dt);
+            }
+            _ => {}
+        }
+    }
+}
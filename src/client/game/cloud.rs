@@ -12,12 +12,10 @@ use crate::{
             utils::{Immediates, Vertex, load_rgba, read_wgsl},
         },
     },
-    server::{
-        ServerEvent,
-        game::{
-            clock::Stage,
-            world::block::{Block, area::BlockArea},
-        },
+    server::game::{
+        clock::{Stage, Time},
+        weather::WeatherState,
+        world::block::{Block, area::BlockArea},
     },
     shared::color::{Float3, Rgb, Rgba},
 };
@@ -80,7 +78,7 @@ impl CloudLayer {
             program,
             blender,
             imm: CloudImmediates::new(image.dimensions()),
-            opacity: Self::opacity(Default::default()),
+            opacity: Self::opacity(Default::default(), Default::default()),
         }
     }
 
@@ -145,28 +143,25 @@ impl CloudLayer {
         })
     }
 
-    fn opacity(stage: Stage) -> f32 {
-        stage.lerp(
+    fn opacity(stage: Stage, weather: WeatherState) -> f32 {
+        let opacity = stage.lerp(
             CLIENT_CONFIG.cloud.day.color.a,
             CLIENT_CONFIG.cloud.night.color.a,
-        )
+        );
+        let factor = weather.blend(|kind| CLIENT_CONFIG.weather.kinds[kind].cloud_opacity);
+        (opacity * factor).min(1.0)
     }
 }
 
 impl EventHandler for CloudLayer {
-    type Context<'a> = Duration;
-
-    fn handle(&mut self, event: &Event, dt: Self::Context<'_>) {
-        match event {
-            Event::ServerEvent(ServerEvent::TimeUpdated(time)) => {
-                let stage = time.stage();
-                self.imm.update_color(stage);
-                self.opacity = Self::opacity(stage);
-            }
-            Event::WindowEvent(WindowEvent::RedrawRequested) => {
-                self.imm.update_offset(dt);
-            }
-            _ => {}
+    type Context<'a> = (Duration, Time, WeatherState);
+
+    fn handle(&mut self, event: &Event, (dt, time, weather): Self::Context<'_>) {
+        if let Event::WindowEvent(WindowEvent::RedrawRequested) = event {
+            let stage = time.stage();
+            self.imm.update_color(stage, weather);
+            self.opacity = Self::opacity(stage, weather);
+            self.imm.update_offset(dt);
         }
     }
 }
@@ -207,14 +202,14 @@ impl CloudImmediates {
             dims: point![width, height].cast(),
             size: CLIENT_CONFIG.cloud.size.cast(),
             scale_factor: Self::scale_factor().into(),
-            color: Self::color(Default::default()).into(),
+            color: Self::color(Default::default(), Default::default()).into(),
             offset: Default::default(),
             padding: Default::default(),
         }
     }
 
-    fn update_color(&mut self, stage: Stage) {
-        self.color = Self::color(stage).into();
+    fn update_color(&mut self, stage: Stage, weather: WeatherState) {
+        self.color = Self::color(stage, weather).into();
     }
 
     fn update_offset(&mut self, dt: Duration) {
@@ -228,11 +223,12 @@ impl CloudImmediates {
         size.map(|c| 1.0 + padding * 2.0 / c as f32)
     }
 
-    fn color(stage: Stage) -> Rgb<f32> {
-        stage.lerp(
+    fn color(stage: Stage, weather: WeatherState) -> Rgb<f32> {
+        let color = stage.lerp(
             CLIENT_CONFIG.cloud.day.color.rgb,
             CLIENT_CONFIG.cloud.night.color.rgb,
-        )
+        );
+        color * weather.blend(|kind| CLIENT_CONFIG.weather.kinds[kind].cloud_color)
     }
 }
 
@@ -0,0 +1,259 @@
+use super::world::BlockVertex;
+use crate::{
+    client::{
+        CLIENT_CONFIG,
+        event_loop::{Event, EventHandler},
+        renderer::{
+            Renderer,
+            buffer::{MemoryState, VertexBuffer},
+            effect::PostProcessor,
+            program::Program,
+            texture::screen::DepthBuffer,
+            utils::{Immediates, Vertex, read_wgsl},
+        },
+    },
+    server::{
+        ServerEvent, event_loop,
+        game::world::{
+            block::{Block, area::BlockArea},
+            entity::{EntityId, EntityKind},
+        },
+    },
+    shared::utils,
+};
+use bytemuck::{Pod, Zeroable};
+use nalgebra::{Matrix4, Point3};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::time::Duration;
+use winit::event::WindowEvent;
+
+pub struct EntityLayer {
+    entities: FxHashMap<EntityId, Entity>,
+    meshes: FxHashMap<Block, Option<VertexBuffer<BlockVertex>>>,
+    program: Program,
+}
+
+impl EntityLayer {
+    pub fn new(
+        renderer: &Renderer,
+        player_bind_group_layout: &wgpu::BindGroupLayout,
+        sky_bind_group_layout: &wgpu::BindGroupLayout,
+        textures_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        for (name, model) in [
+            ("item", &CLIENT_CONFIG.entity.item),
+            ("mob", &CLIENT_CONFIG.entity.mob),
+        ] {
+            if let Some(part) = model
+                .parts
+                .iter()
+                .find(|part| (part.max - part.min).min() <= 0.0)
+            {
+                panic!(
+                    "invalid part [{}, {}] of entity model \"{name}\", expected min < max",
+                    part.min, part.max,
+                );
+            }
+        }
+
+        Self {
+            entities: Default::default(),
+            meshes: Default::default(),
+            program: Program::builder()
+                .renderer(renderer)
+                .shader_desc(read_wgsl("assets/shaders/entity.wgsl"))
+                .bind_group_layouts(&[
+                    player_bind_group_layout,
+                    sky_bind_group_layout,
+                    textures_bind_group_layout,
+                ])
+                .immediate_size(EntityImmediates::SIZE)
+                .buffers(&[BlockVertex::desc()])
+                .cull_mode(wgpu::Face::Back)
+                .depth_stencil(wgpu::DepthStencilState {
+                    format: DepthBuffer::FORMAT,
+                    depth_write_enabled: Some(true),
+                    depth_compare: Some(wgpu::CompareFunction::Less),
+                    stencil: Default::default(),
+                    bias: Default::default(),
+                })
+                .format(PostProcessor::FORMAT)
+                .blend(wgpu::BlendState::ALPHA_BLENDING)
+                .build(),
+        }
+    }
+
+    pub fn draw(
+        &self,
+        view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+        player_bind_group: &wgpu::BindGroup,
+        sky_bind_group: &wgpu::BindGroup,
+        textures_bind_group: &wgpu::BindGroup,
+        depth_view: &wgpu::TextureView,
+    ) {
+        if self.entities.is_empty() {
+            return;
+        }
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+
+        self.program.bind(
+            &mut render_pass,
+            [player_bind_group, sky_bind_group, textures_bind_group],
+        );
+
+        for entity in self.entities.values() {
+            if let Some(Some(mesh)) = self.meshes.get(&entity.kind.block()) {
+                for part in &entity.model().parts {
+                    EntityImmediates::new(entity, part).set(&mut render_pass);
+                    mesh.draw(&mut render_pass);
+                }
+            }
+        }
+    }
+
+    fn mesh(renderer: &Renderer, block: Block) -> Option<VertexBuffer<BlockVertex>> {
+        let vertices = block
+            .data()
+            .mesh(
+                Default::default(),
+                &BlockArea::default().with_kernel(block),
+                &Default::default(),
+            )
+            .collect::<Vec<_>>();
+        VertexBuffer::try_new(renderer, MemoryState::Immutable(&vertices))
+    }
+}
+
+impl EventHandler for EntityLayer {
+    type Context<'a> = (&'a Renderer, Duration);
+
+    fn handle(&mut self, event: &Event, (renderer, dt): Self::Context<'_>) {
+        match *event {
+            Event::ServerEvent(ServerEvent::EntitySpawned {
+                id, kind, origin, ..
+            }) => {
+                self.entities.insert(id, Entity::new(kind, origin));
+            }
+            Event::ServerEvent(ServerEvent::EntityMoved { id, origin, .. }) => {
+                if let Some(entity) = self.entities.get_mut(&id) {
+                    entity.move_to(origin);
+                }
+            }
+            Event::ServerEvent(ServerEvent::EntityDespawned(id)) => {
+                self.entities.remove(&id);
+            }
+            Event::ServerEvent(ServerEvent::WorldInitialized { .. }) => {
+                self.entities.clear();
+            }
+            Event::WindowEvent(WindowEvent::RedrawRequested) => {
+                let progress = dt.as_secs_f32() / event_loop::tick_duration().as_secs_f32();
+                for entity in self.entities.values_mut() {
+                    entity.progress = (entity.progress + progress).min(1.0);
+                    let block = entity.kind.block();
+                    self.meshes
+                        .entry(block)
+                        .or_insert_with(|| Self::mesh(renderer, block));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Entity {
+    kind: EntityKind,
+    prev: Point3<f32>,
+    cur: Point3<f32>,
+    progress: f32,
+}
+
+impl Entity {
+    fn new(kind: EntityKind, origin: Point3<f32>) -> Self {
+        Self {
+            kind,
+            prev: origin,
+            cur: origin,
+            progress: 1.0,
+        }
+    }
+
+    fn move_to(&mut self, origin: Point3<f32>) {
+        self.prev = self.origin();
+        self.cur = origin;
+        self.progress = 0.0;
+    }
+
+    fn origin(&self) -> Point3<f32> {
+        Point3::from(utils::lerp(
+            self.prev.coords,
+            self.cur.coords,
+            self.progress,
+        ))
+    }
+
+    fn model(&self) -> &'static EntityModel {
+        match self.kind {
+            EntityKind::Item(_) => &CLIENT_CONFIG.entity.item,
+            EntityKind::Mob { .. } => &CLIENT_CONFIG.entity.mob,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct EntityImmediates {
+    m: Matrix4<f32>,
+}
+
+impl EntityImmediates {
+    fn new(entity: &Entity, part: &ModelPart) -> Self {
+        let diagonal = entity.kind.diagonal();
+        Self {
+            m: Matrix4::new_translation(&(entity.origin().coords - diagonal * 0.5))
+                .prepend_nonuniform_scaling(&diagonal)
+                .prepend_translation(&part.min.coords)
+                .prepend_nonuniform_scaling(&(part.max - part.min)),
+        }
+    }
+}
+
+impl Immediates for EntityImmediates {}
+
+#[derive(Deserialize)]
+pub struct EntityConfig {
+    item: EntityModel,
+    mob: EntityModel,
+}
+
+#[derive(Deserialize)]
+struct EntityModel {
+    parts: Vec<ModelPart>,
+}
+
+#[derive(Deserialize)]
+struct ModelPart {
+    min: Point3<f32>,
+    max: Point3<f32>,
+}
@@ -0,0 +1,332 @@
+use super::Gui;
+use crate::{
+    client::{
+        CLIENT_CONFIG, ClientEvent,
+        event_loop::{Event, EventHandler},
+        renderer::{
+            Renderer, Surface,
+            buffer::{MemoryState, VertexBuffer},
+            effect::PostProcessor,
+            program::Program,
+            texture::image::ImageTexture,
+            uniform::Uniform,
+            utils::{Vertex, load_rgba, read_wgsl},
+        },
+    },
+    server::ServerEvent,
+};
+use bytemuck::{Pod, Zeroable};
+use crossbeam_channel::Sender;
+use nalgebra::{Matrix4, Vector2, Vector4, vector};
+use serde::Deserialize;
+use std::{collections::VecDeque, mem, time::Duration};
+use winit::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    keyboard::{KeyCode, PhysicalKey},
+};
+
+pub struct Chat {
+    vertex_buffer: Option<VertexBuffer<GlyphVertex>>,
+    uniform: Uniform<ChatUniformData>,
+    texture: ImageTexture,
+    program: Program,
+    input: Option<String>,
+    messages: VecDeque<Message>,
+    visible_count: usize,
+    is_updated: bool,
+}
+
+impl Chat {
+    const COLUMNS: u8 = 16;
+    const ROWS: u8 = 6;
+    pub(super) const GLYPH_WIDTH: f32 = 0.75;
+    pub(super) const PADDING: f32 = 0.25;
+    pub(super) const BACKGROUND: char = '\x7f';
+    pub(super) const BACKGROUND_COLOR: Vector4<f32> = vector![0.0, 0.0, 0.0, 0.5];
+    const INPUT_COLOR: Vector4<f32> = vector![1.0, 1.0, 1.0, 1.0];
+    pub(super) const ECHO_COLOR: Vector4<f32> = vector![0.7, 0.7, 0.7, 1.0];
+    pub(super) const FEEDBACK_COLOR: Vector4<f32> = vector![1.0, 1.0, 1.0, 1.0];
+    const ERROR_COLOR: Vector4<f32> = vector![1.0, 0.35, 0.35, 1.0];
+
+    pub fn new(renderer: &Renderer, surface: &Surface) -> Self {
+        let uniform = Uniform::new(renderer, MemoryState::UNINIT, wgpu::ShaderStages::VERTEX);
+        let texture = ImageTexture::builder()
+            .renderer(renderer)
+            .surface(surface)
+            .image(load_rgba("assets/textures/gui/font.png"))
+            .is_srgb(false)
+            .build();
+        let program = Program::builder()
+            .renderer(renderer)
+            .shader_desc(read_wgsl("assets/shaders/text.wgsl"))
+            .bind_group_layouts(&[uniform.bind_group_layout(), texture.bind_group_layout()])
+            .buffers(&[GlyphVertex::desc()])
+            .format(PostProcessor::FORMAT)
+            .blend(wgpu::BlendState::ALPHA_BLENDING)
+            .build();
+        Self {
+            vertex_buffer: None,
+            uniform,
+            texture,
+            program,
+            input: None,
+            messages: Default::default(),
+            visible_count: 0,
+            is_updated: false,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass) {
+        if let Some(buffer) = &self.vertex_buffer {
+            self.program.bind(
+                render_pass,
+                [self.uniform.bind_group(), self.texture.bind_group()],
+            );
+            buffer.draw(render_pass);
+        }
+    }
+
+    fn push_message(&mut self, text: &str, color: Vector4<f32>) {
+        for line in text.lines() {
+            self.messages.push_front(Message {
+                text: line.into(),
+                color,
+                age: Duration::ZERO,
+            });
+        }
+        self.messages.truncate(CLIENT_CONFIG.gui.chat.max_messages);
+        self.is_updated = true;
+    }
+
+    fn type_key(&mut self, keycode: KeyCode, text: Option<&str>, client_tx: &Sender<ClientEvent>) {
+        let Some(input) = &mut self.input else {
+            self.input = match keycode {
+                KeyCode::KeyT => Some(String::new()),
+                KeyCode::Slash => Some("/".into()),
+                _ => return,
+            };
+            self.is_updated = true;
+            return;
+        };
+
+        match keycode {
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                let input = mem::take(input);
+                self.input = None;
+                if !input.trim().is_empty() {
+                    self.push_message(&input, Self::ECHO_COLOR);
+                    _ = client_tx.send(ClientEvent::CommandSubmitted(input));
+                }
+            }
+            KeyCode::Escape => {
+                self.input = None;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            _ => {
+                let Some(text) = text else {
+                    return;
+                };
+                input.extend(text.chars().filter(|&c| c == ' ' || c.is_ascii_graphic()));
+            }
+        }
+        self.is_updated = true;
+    }
+
+    fn visible_count(&self) -> usize {
+        if self.is_open() {
+            self.messages.len()
+        } else {
+            let duration = Duration::from_secs_f32(CLIENT_CONFIG.gui.chat.message_duration);
+            self.messages
+                .iter()
+                .take_while(|message| message.age < duration)
+                .count()
+        }
+    }
+
+    fn vertices(&self) -> Vec<GlyphVertex> {
+        let mut vertices = vec![];
+        let input = self.input.as_ref().map(|input| format!("{input}_"));
+        let lines = input
+            .iter()
+            .map(|input| (&**input, Self::INPUT_COLOR))
+            .chain(
+                self.messages
+                    .iter()
+                    .take(self.visible_count)
+                    .map(|message| (&*message.text, message.color)),
+            );
+
+        for (row, (text, color)) in lines.enumerate() {
+            let y = row as f32;
+            let width = text.chars().count() as f32 * Self::GLYPH_WIDTH;
+            Self::push_quad(
+                &mut vertices,
+                vector![-Self::PADDING, y],
+                vector![width + Self::PADDING * 2.0, 1.0],
+                Self::BACKGROUND,
+                Self::BACKGROUND_COLOR,
+            );
+            for (col, c) in text.chars().enumerate() {
+                if c != ' ' {
+                    Self::push_quad(
+                        &mut vertices,
+                        vector![col as f32 * Self::GLYPH_WIDTH, y],
+                        vector![Self::GLYPH_WIDTH, 1.0],
+                        c,
+                        color,
+                    );
+                }
+            }
+        }
+
+        vertices
+    }
+
+    pub(super) fn push_quad(
+        vertices: &mut Vec<GlyphVertex>,
+        min: Vector2<f32>,
+        size: Vector2<f32>,
+        c: char,
+        color: Vector4<f32>,
+    ) {
+        let glyph = if (' '..=Self::BACKGROUND).contains(&c) {
+            c as u8 - b' '
+        } else {
+            b'?' - b' '
+        };
+        let tex_size = vector![1.0 / Self::COLUMNS as f32, 1.0 / Self::ROWS as f32];
+        let tex_min = vector![
+            (glyph % Self::COLUMNS) as f32,
+            (glyph / Self::COLUMNS) as f32
+        ]
+        .component_mul(&tex_size);
+
+        let corner = |x: f32, y: f32| {
+            let tex_coords = if c == Self::BACKGROUND {
+                tex_min + tex_size * 0.5
+            } else {
+                tex_min + vector![x, 1.0 - y].component_mul(&tex_size)
+            };
+            GlyphVertex {
+                coords: min + vector![x, y].component_mul(&size),
+                tex_coords,
+                color,
+            }
+        };
+
+        vertices.extend([
+            corner(0.0, 0.0),
+            corner(1.0, 0.0),
+            corner(1.0, 1.0),
+            corner(1.0, 1.0),
+            corner(0.0, 1.0),
+            corner(0.0, 0.0),
+        ]);
+    }
+}
+
+impl EventHandler for Chat {
+    type Context<'a> = (&'a Sender<ClientEvent>, &'a Renderer, &'a Surface, Duration);
+
+    fn handle(&mut self, event: &Event, (client_tx, renderer, surface, dt): Self::Context<'_>) {
+        match event {
+            Event::ServerEvent(ServerEvent::CommandFeedback { message, is_error }) => {
+                let color = if *is_error {
+                    Self::ERROR_COLOR
+                } else {
+                    Self::FEEDBACK_COLOR
+                };
+                self.push_message(message, color);
+            }
+            Event::WindowEvent(event) => match event {
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            physical_key: PhysicalKey::Code(keycode),
+                            state: ElementState::Pressed,
+                            text,
+                            ..
+                        },
+                    ..
+                } => {
+                    self.type_key(*keycode, text.as_deref(), client_tx);
+                }
+                WindowEvent::RedrawRequested => {
+                    for message in &mut self.messages {
+                        message.age += dt;
+                    }
+
+                    let visible_count = self.visible_count();
+                    if mem::replace(&mut self.visible_count, visible_count) != visible_count {
+                        self.is_updated = true;
+                    }
+
+                    if mem::take(&mut self.is_updated) {
+                        self.vertex_buffer = VertexBuffer::try_new(
+                            renderer,
+                            MemoryState::Immutable(&self.vertices()),
+                        );
+                    }
+
+                    if surface.is_resized {
+                        self.uniform.set(renderer, &ChatUniformData::new(surface));
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+struct Message {
+    text: String,
+    color: Vector4<f32>,
+    age: Duration,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+pub(super) struct GlyphVertex {
+    coords: Vector2<f32>,
+    tex_coords: Vector2<f32>,
+    color: Vector4<f32>,
+}
+
+impl Vertex for GlyphVertex {
+    const ATTRIBS: &[wgpu::VertexAttribute] =
+        &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4];
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct ChatUniformData {
+    transform: Matrix4<f32>,
+}
+
+impl ChatUniformData {
+    fn new(surface: &Surface) -> Self {
+        let scaling = Gui::scaling(
+            surface.width(),
+            surface.height(),
+            CLIENT_CONFIG.gui.chat.size,
+        );
+        Self {
+            transform: Gui::transform(scaling, scaling),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ChatConfig {
+    size: f32,
+    message_duration: f32,
+    max_messages: usize,
+}
@@ -0,0 +1,289 @@
+use super::{
+    Gui,
+    chat::{Chat, GlyphVertex},
+};
+use crate::{
+    client::{
+        CLIENT_CONFIG, ClientEvent,
+        event_loop::{Event, EventHandler},
+        renderer::{
+            Renderer, Surface,
+            buffer::{MemoryState, VertexBuffer},
+            effect::PostProcessor,
+            program::Program,
+            texture::image::ImageTexture,
+            uniform::Uniform,
+            utils::{Vertex, load_rgba, read_wgsl},
+        },
+    },
+    server::{
+        ServerEvent,
+        game::{
+            inventory::Stack,
+            recipe::{CraftingGrid, GRID_DIM, Recipe},
+            world::block::interaction::GuiKind,
+        },
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use crossbeam_channel::Sender;
+use nalgebra::{Matrix4, Vector2, Vector4, vector};
+use serde::Deserialize;
+use std::mem;
+use winit::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    keyboard::{KeyCode, PhysicalKey},
+};
+
+pub struct Crafting {
+    vertex_buffer: Option<VertexBuffer<GlyphVertex>>,
+    uniform: Uniform<CraftingUniformData>,
+    texture: ImageTexture,
+    program: Program,
+    grid: CraftingGrid,
+    cursor: (usize, usize),
+    held: Option<Stack>,
+    size: Vector2<f32>,
+    is_open: bool,
+    is_updated: bool,
+}
+
+impl Crafting {
+    const CELL_WIDTH: usize = 10;
+    const CURSOR_COLOR: Vector4<f32> = vector![1.0, 0.85, 0.3, 1.0];
+    const HELP: &[&str] = &[
+        "arrows: move, space: put, backspace: clear",
+        "enter: craft, c: close",
+    ];
+
+    pub fn new(renderer: &Renderer, surface: &Surface) -> Self {
+        let uniform = Uniform::new(renderer, MemoryState::UNINIT, wgpu::ShaderStages::VERTEX);
+        let texture = ImageTexture::builder()
+            .renderer(renderer)
+            .surface(surface)
+            .image(load_rgba("assets/textures/gui/font.png"))
+            .is_srgb(false)
+            .build();
+        let program = Program::builder()
+            .renderer(renderer)
+            .shader_desc(read_wgsl("assets/shaders/text.wgsl"))
+            .bind_group_layouts(&[uniform.bind_group_layout(), texture.bind_group_layout()])
+            .buffers(&[GlyphVertex::desc()])
+            .format(PostProcessor::FORMAT)
+            .blend(wgpu::BlendState::ALPHA_BLENDING)
+            .build();
+        Self {
+            vertex_buffer: None,
+            uniform,
+            texture,
+            program,
+            grid: Default::default(),
+            cursor: (0, 0),
+            held: None,
+            size: Default::default(),
+            is_open: false,
+            is_updated: false,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass) {
+        if let Some(buffer) = &self.vertex_buffer {
+            self.program.bind(
+                render_pass,
+                [self.uniform.bind_group(), self.texture.bind_group()],
+            );
+            buffer.draw(render_pass);
+        }
+    }
+
+    fn press_key(&mut self, keycode: KeyCode, client_tx: &Sender<ClientEvent>) {
+        if !self.is_open {
+            if keycode == KeyCode::KeyC {
+                self.is_open = true;
+                self.is_updated = true;
+            }
+            return;
+        }
+
+        let (row, col) = &mut self.cursor;
+        match keycode {
+            KeyCode::KeyC | KeyCode::Escape => self.is_open = false,
+            KeyCode::ArrowUp => *row = row.saturating_sub(1),
+            KeyCode::ArrowDown => *row = (*row + 1).min(GRID_DIM - 1),
+            KeyCode::ArrowLeft => *col = col.saturating_sub(1),
+            KeyCode::ArrowRight => *col = (*col + 1).min(GRID_DIM - 1),
+            KeyCode::Space => self.grid[*row][*col] = self.held.map(|stack| stack.block),
+            KeyCode::Backspace | KeyCode::Delete => self.grid[*row][*col] = None,
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                _ = client_tx.send(ClientEvent::CraftRequested(self.grid));
+                return;
+            }
+            _ => return,
+        }
+        self.is_updated = true;
+    }
+
+    fn lines(&self) -> Vec<Vec<(String, Vector4<f32>)>> {
+        let mut lines = vec![vec![("crafting".into(), Chat::FEEDBACK_COLOR)]];
+
+        for (row, blocks) in self.grid.iter().enumerate() {
+            lines.push(
+                blocks
+                    .iter()
+                    .enumerate()
+                    .map(|(col, block)| {
+                        let name = block.map_or("-", |block| block.name());
+                        let color = if (row, col) == self.cursor {
+                            Self::CURSOR_COLOR
+                        } else {
+                            Chat::FEEDBACK_COLOR
+                        };
+                        (format!("{name:^w$.w$}", w = Self::CELL_WIDTH), color)
+                    })
+                    .collect(),
+            );
+        }
+
+        let output = Recipe::find(&self.grid)
+            .map_or_else(|| "nothing".into(), |recipe| recipe.output.to_string());
+        let held = self
+            .held
+            .map_or_else(|| "nothing".into(), |stack| stack.to_string());
+        lines.push(vec![(format!("makes {output}"), Chat::FEEDBACK_COLOR)]);
+        lines.push(vec![(format!("holding {held}"), Chat::FEEDBACK_COLOR)]);
+        lines.extend(
+            Self::HELP
+                .iter()
+                .map(|&help| vec![(help.into(), Chat::ECHO_COLOR)]),
+        );
+
+        lines
+    }
+
+    fn vertices(&self) -> (Vec<GlyphVertex>, Vector2<f32>) {
+        let lines = self.lines();
+        let columns = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(text, _)| text.chars().count())
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or_default();
+        let width = columns as f32 * Chat::GLYPH_WIDTH;
+        let height = lines.len() as f32;
+
+        let mut vertices = vec![];
+        for (row, line) in lines.into_iter().enumerate() {
+            let y = height - 1.0 - row as f32;
+            Chat::push_quad(
+                &mut vertices,
+                vector![-Chat::PADDING, y],
+                vector![width + Chat::PADDING * 2.0, 1.0],
+                Chat::BACKGROUND,
+                Chat::BACKGROUND_COLOR,
+            );
+            let mut col = 0;
+            for (text, color) in line {
+                for c in text.chars() {
+                    if c != ' ' {
+                        Chat::push_quad(
+                            &mut vertices,
+                            vector![col as f32 * Chat::GLYPH_WIDTH, y],
+                            vector![Chat::GLYPH_WIDTH, 1.0],
+                            c,
+                            color,
+                        );
+                    }
+                    col += 1;
+                }
+            }
+        }
+
+        (vertices, vector![width, height])
+    }
+}
+
+impl EventHandler for Crafting {
+    type Context<'a> = (
+        &'a Sender<ClientEvent>,
+        &'a Renderer,
+        &'a Surface,
+        Option<Stack>,
+    );
+
+    fn handle(&mut self, event: &Event, (client_tx, renderer, surface, held): Self::Context<'_>) {
+        match event {
+            Event::WindowEvent(WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        physical_key: PhysicalKey::Code(keycode),
+                        state: ElementState::Pressed,
+                        ..
+                    },
+                ..
+            }) => {
+                self.press_key(*keycode, client_tx);
+            }
+            Event::ServerEvent(ServerEvent::GuiOpened(GuiKind::Crafting)) => {
+                self.is_open = true;
+                self.is_updated = true;
+            }
+            Event::WindowEvent(WindowEvent::RedrawRequested) => {
+                if mem::replace(&mut self.held, held) != held {
+                    self.is_updated = true;
+                }
+
+                let mut is_transform_outdated = surface.is_resized;
+
+                if mem::take(&mut self.is_updated) {
+                    self.vertex_buffer = None;
+                    if self.is_open {
+                        let (vertices, size) = self.vertices();
+                        self.vertex_buffer =
+                            VertexBuffer::try_new(renderer, MemoryState::Immutable(&vertices));
+                        if mem::replace(&mut self.size, size) != size {
+                            is_transform_outdated = true;
+                        }
+                    }
+                }
+
+                if is_transform_outdated {
+                    self.uniform
+                        .set(renderer, &CraftingUniformData::new(surface, self.size));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct CraftingUniformData {
+    transform: Matrix4<f32>,
+}
+
+impl CraftingUniformData {
+    fn new(surface: &Surface, size: Vector2<f32>) -> Self {
+        let scaling = Gui::scaling(
+            surface.width(),
+            surface.height(),
+            CLIENT_CONFIG.gui.crafting.size,
+        );
+        let offset = Vector2::repeat(0.5) - scaling.component_mul(&size) * 0.5;
+        Self {
+            transform: Gui::transform(scaling, offset),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CraftingConfig {
+    size: f32,
+}
@@ -16,7 +16,10 @@ use crate::{
     },
     server::{
         ServerEvent,
-        game::world::block::{Block, area::BlockArea},
+        game::{
+            inventory::Stack,
+            world::block::{Block, area::BlockArea},
+        },
     },
 };
 use bytemuck::{Pod, Zeroable};
@@ -25,7 +28,6 @@ use serde::Deserialize;
 use std::{
     f32::consts::{FRAC_PI_4, FRAC_PI_6},
     mem,
-    sync::Arc,
 };
 use winit::{
     event::{ElementState, KeyEvent, WindowEvent},
@@ -36,13 +38,15 @@ pub struct Inventory {
     vertex_buffer: Option<VertexBuffer<BlockVertex>>,
     uniform: Uniform<InventoryUniformData>,
     program: Program,
-    contents: Arc<[Block]>,
+    contents: [Option<Stack>; Self::SLOT_COUNT],
     index: usize,
     is_flat: bool,
     is_updated: bool,
 }
 
 impl Inventory {
+    const SLOT_COUNT: usize = 9;
+
     pub fn new(renderer: &Renderer, textures_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
         let uniform = Uniform::new(renderer, MemoryState::UNINIT, wgpu::ShaderStages::VERTEX);
         let program = Program::builder()
@@ -72,8 +76,23 @@ impl Inventory {
         }
     }
 
+    pub fn selected_stack(&self) -> Option<Stack> {
+        self.contents[self.index]
+    }
+
     pub fn selected_block(&self) -> Option<Block> {
-        self.contents.get(self.index).copied()
+        self.selected_stack().map(|stack| stack.block)
+    }
+
+    pub fn pick_block(&mut self, block: Block) {
+        let block = block.base();
+        if let Some(idx) = self
+            .contents
+            .iter()
+            .position(|stack| stack.is_some_and(|stack| stack.block == block))
+        {
+            self.is_updated |= mem::replace(&mut self.index, idx) != idx;
+        }
     }
 
     pub fn draw(&self, render_pass: &mut wgpu::RenderPass, textures_bind_group: &wgpu::BindGroup) {
@@ -107,8 +126,11 @@ impl EventHandler for Inventory {
 
     fn handle(&mut self, event: &Event, (renderer, surface): Self::Context<'_>) {
         match event {
-            Event::ServerEvent(ServerEvent::PlayerInitialized { inventory, .. }) => {
-                self.contents = inventory.clone();
+            Event::ServerEvent(
+                ServerEvent::PlayerInitialized { inventory, .. }
+                | ServerEvent::InventoryUpdated(inventory),
+            ) => {
+                self.contents.copy_from_slice(inventory);
                 self.is_updated = true;
             }
             Event::WindowEvent(event) => match *event {
@@ -122,7 +144,7 @@ impl EventHandler for Inventory {
                     ..
                 } => {
                     if let Some(idx) = Self::index(keycode) {
-                        self.is_updated = mem::replace(&mut self.index, idx) != idx;
+                        self.is_updated |= mem::replace(&mut self.index, idx) != idx;
                     }
                 }
                 WindowEvent::RedrawRequested => {
@@ -1,8 +1,12 @@
+pub mod chat;
+pub mod crafting;
 pub mod crosshair;
 pub mod inventory;
+pub mod tooltip;
 
 use crate::{
     client::{
+        ClientEvent,
         event_loop::{Event, EventHandler},
         renderer::{
             Renderer, Surface,
@@ -11,15 +15,24 @@ use crate::{
     },
     server::game::world::block::Block,
 };
+use chat::{Chat, ChatConfig};
+use crafting::{Crafting, CraftingConfig};
+use crossbeam_channel::Sender;
 use crosshair::{Crosshair, CrosshairConfig};
 use inventory::{Inventory, InventoryConfig};
 use nalgebra::{Matrix4, Vector2, vector};
 use serde::Deserialize;
+use std::time::Duration;
+use tooltip::{Tooltip, TooltipConfig};
+use winit::event::{ElementState, KeyEvent, WindowEvent};
 
 pub struct Gui {
     blit: Blit,
     crosshair: Crosshair,
     inventory: Inventory,
+    chat: Chat,
+    crafting: Crafting,
+    tooltip: Tooltip,
 }
 
 impl Gui {
@@ -33,6 +46,9 @@ impl Gui {
             blit: Blit::new(renderer, input_bind_group_layout, PostProcessor::FORMAT),
             crosshair: Crosshair::new(renderer, surface, input_bind_group_layout),
             inventory: Inventory::new(renderer, textures_bind_group_layout),
+            chat: Chat::new(renderer, surface),
+            crafting: Crafting::new(renderer, surface),
+            tooltip: Tooltip::new(renderer, surface),
         }
     }
 
@@ -40,6 +56,18 @@ impl Gui {
         self.inventory.selected_block()
     }
 
+    pub fn pick_block(&mut self, block: Block) {
+        self.inventory.pick_block(block);
+    }
+
+    pub fn show_tooltip(&mut self, text: Option<String>) {
+        self.tooltip.show(text);
+    }
+
+    pub fn is_typing(&self, event: &Event) -> bool {
+        (self.chat.is_open() || self.crafting.is_open()) && Self::is_key_pressed(event)
+    }
+
     pub fn draw(
         &self,
         view: &wgpu::TextureView,
@@ -63,6 +91,9 @@ impl Gui {
             });
             self.blit.draw(&mut render_pass, input_bind_group);
             self.crosshair.draw(&mut render_pass, input_bind_group);
+            self.tooltip.draw(&mut render_pass);
+            self.chat.draw(&mut render_pass);
+            self.crafting.draw(&mut render_pass);
         }
         self.inventory.draw(
             &mut encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -89,6 +120,19 @@ impl Gui {
         );
     }
 
+    fn is_key_pressed(event: &Event) -> bool {
+        matches!(
+            event,
+            Event::WindowEvent(WindowEvent::KeyboardInput {
+                event: KeyEvent {
+                    state: ElementState::Pressed,
+                    ..
+                },
+                ..
+            })
+        )
+    }
+
     fn scaling(width: f32, height: f32, factor: f32) -> Vector2<f32> {
         let size = (height * 0.0325).max(13.5) * factor;
         vector![size / width, size / height]
@@ -103,11 +147,21 @@ impl Gui {
 }
 
 impl EventHandler for Gui {
-    type Context<'a> = (&'a Renderer, &'a Surface);
+    type Context<'a> = (&'a Sender<ClientEvent>, &'a Renderer, &'a Surface, Duration);
 
-    fn handle(&mut self, event: &Event, (renderer, surface): Self::Context<'_>) {
+    fn handle(&mut self, event: &Event, (client_tx, renderer, surface, dt): Self::Context<'_>) {
+        let is_key_pressed = Self::is_key_pressed(event);
         self.crosshair.handle(event, (renderer, surface));
-        self.inventory.handle(event, (renderer, surface));
+        self.tooltip.handle(event, (renderer, surface));
+        if !(is_key_pressed && self.crafting.is_open()) {
+            self.chat.handle(event, (client_tx, renderer, surface, dt));
+        }
+        if !(is_key_pressed && self.chat.is_open()) {
+            self.inventory.handle(event, (renderer, surface));
+            let held = self.inventory.selected_stack();
+            self.crafting
+                .handle(event, (client_tx, renderer, surface, held));
+        }
     }
 }
 
@@ -115,4 +169,7 @@ impl EventHandler for Gui {
 pub struct GuiConfig {
     crosshair: CrosshairConfig,
     inventory: InventoryConfig,
+    chat: ChatConfig,
+    crafting: CraftingConfig,
+    tooltip: TooltipConfig,
 }
@@ -0,0 +1,155 @@
+use super::{
+    Gui,
+    chat::{Chat, GlyphVertex},
+};
+use crate::client::{
+    CLIENT_CONFIG,
+    event_loop::{Event, EventHandler},
+    renderer::{
+        Renderer, Surface,
+        buffer::{MemoryState, VertexBuffer},
+        effect::PostProcessor,
+        program::Program,
+        texture::image::ImageTexture,
+        uniform::Uniform,
+        utils::{Vertex, load_rgba, read_wgsl},
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use nalgebra::{Matrix4, Vector2, vector};
+use serde::Deserialize;
+use std::mem;
+use winit::event::WindowEvent;
+
+pub struct Tooltip {
+    vertex_buffer: Option<VertexBuffer<GlyphVertex>>,
+    uniform: Uniform<TooltipUniformData>,
+    texture: ImageTexture,
+    program: Program,
+    text: Option<String>,
+    width: f32,
+    is_updated: bool,
+}
+
+impl Tooltip {
+    pub fn new(renderer: &Renderer, surface: &Surface) -> Self {
+        let uniform = Uniform::new(renderer, MemoryState::UNINIT, wgpu::ShaderStages::VERTEX);
+        let texture = ImageTexture::builder()
+            .renderer(renderer)
+            .surface(surface)
+            .image(load_rgba("assets/textures/gui/font.png"))
+            .is_srgb(false)
+            .build();
+        let program = Program::builder()
+            .renderer(renderer)
+            .shader_desc(read_wgsl("assets/shaders/text.wgsl"))
+            .bind_group_layouts(&[uniform.bind_group_layout(), texture.bind_group_layout()])
+            .buffers(&[GlyphVertex::desc()])
+            .format(PostProcessor::FORMAT)
+            .blend(wgpu::BlendState::ALPHA_BLENDING)
+            .build();
+        Self {
+            vertex_buffer: None,
+            uniform,
+            texture,
+            program,
+            text: None,
+            width: 0.0,
+            is_updated: false,
+        }
+    }
+
+    pub fn show(&mut self, text: Option<String>) {
+        if self.text != text {
+            self.text = text;
+            self.is_updated = true;
+        }
+    }
+
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass) {
+        if let Some(buffer) = &self.vertex_buffer {
+            self.program.bind(
+                render_pass,
+                [self.uniform.bind_group(), self.texture.bind_group()],
+            );
+            buffer.draw(render_pass);
+        }
+    }
+
+    fn vertices(text: &str) -> (Vec<GlyphVertex>, f32) {
+        let width = text.chars().count() as f32 * Chat::GLYPH_WIDTH;
+
+        let mut vertices = vec![];
+        Chat::push_quad(
+            &mut vertices,
+            vector![-Chat::PADDING, 0.0],
+            vector![width + Chat::PADDING * 2.0, 1.0],
+            Chat::BACKGROUND,
+            Chat::BACKGROUND_COLOR,
+        );
+        for (col, c) in text.chars().enumerate() {
+            if c != ' ' {
+                Chat::push_quad(
+                    &mut vertices,
+                    vector![col as f32 * Chat::GLYPH_WIDTH, 0.0],
+                    vector![Chat::GLYPH_WIDTH, 1.0],
+                    c,
+                    Chat::FEEDBACK_COLOR,
+                );
+            }
+        }
+
+        (vertices, width)
+    }
+}
+
+impl EventHandler for Tooltip {
+    type Context<'a> = (&'a Renderer, &'a Surface);
+
+    fn handle(&mut self, event: &Event, (renderer, surface): Self::Context<'_>) {
+        if let Event::WindowEvent(WindowEvent::RedrawRequested) = event {
+            let mut is_transform_outdated = surface.is_resized;
+
+            if mem::take(&mut self.is_updated) {
+                self.vertex_buffer = None;
+                if let Some(text) = &self.text {
+                    let (vertices, width) = Self::vertices(text);
+                    self.vertex_buffer =
+                        VertexBuffer::try_new(renderer, MemoryState::Immutable(&vertices));
+                    if mem::replace(&mut self.width, width) != width {
+                        is_transform_outdated = true;
+                    }
+                }
+            }
+
+            if is_transform_outdated {
+                self.uniform
+                    .set(renderer, &TooltipUniformData::new(surface, self.width));
+            }
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct TooltipUniformData {
+    transform: Matrix4<f32>,
+}
+
+impl TooltipUniformData {
+    fn new(surface: &Surface, width: f32) -> Self {
+        let TooltipConfig { size, offset } = CLIENT_CONFIG.gui.tooltip;
+        let scaling = Gui::scaling(surface.width(), surface.height(), size);
+        let offset =
+            Vector2::repeat(0.5) - scaling.component_mul(&vector![width * 0.5, offset + 1.0]);
+        Self {
+            transform: Gui::transform(scaling, offset),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TooltipConfig {
+    size: f32,
+    offset: f32,
+}
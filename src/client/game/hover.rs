@@ -49,7 +49,10 @@ impl BlockHover {
         sky_bind_group: &wgpu::BindGroup,
         depth_view: &wgpu::TextureView,
     ) {
-        if let Some(BlockHoverData { hitbox, brightness }) = self.data {
+        if let Some(BlockHoverData {
+            hitbox, brightness, ..
+        }) = self.data
+        {
             self.highlight.draw(
                 &mut encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -83,8 +86,10 @@ impl EventHandler for BlockHover {
     type Context<'a> = ();
 
     fn handle(&mut self, event: &Event, (): Self::Context<'_>) {
-        if let Event::ServerEvent(ServerEvent::BlockHovered(data)) = *event {
-            self.data = data;
+        match *event {
+            Event::ServerEvent(ServerEvent::BlockHovered(data)) => self.data = data,
+            Event::ServerEvent(ServerEvent::WorldInitialized { .. }) => self.data = None,
+            _ => {}
         }
     }
 }
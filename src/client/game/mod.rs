@@ -1,9 +1,14 @@
+pub mod block_entity;
+pub mod border;
+pub mod clock;
 pub mod cloud;
+pub mod entity;
 pub mod fog;
 pub mod gui;
 pub mod hover;
 pub mod player;
 pub mod sky;
+pub mod weather;
 pub mod world;
 
 use super::{
@@ -17,8 +22,12 @@ use super::{
     window::RawWindow,
 };
 use crate::{client::renderer::utils::load_rgba, server::game::world::block::data::TEX_PATHS};
+use block_entity::BlockEntities;
+use border::BorderWall;
+use clock::Clock;
 use cloud::CloudLayer;
 use crossbeam_channel::Sender;
+use entity::EntityLayer;
 use fog::Fog;
 use gui::Gui;
 use hover::BlockHover;
@@ -26,15 +35,21 @@ use image::RgbaImage;
 use player::Player;
 use sky::Sky;
 use std::{ops::Deref, time::Duration};
+use weather::WeatherLayer;
 use winit::event::WindowEvent;
 use world::World;
 
 pub struct Game {
+    clock: Clock,
+    weather: WeatherLayer,
     sky: Sky,
     world: World,
+    entities: EntityLayer,
+    border: BorderWall,
     clouds: CloudLayer,
     fog: Fog,
     hover: BlockHover,
+    block_entities: BlockEntities,
     aces: Aces,
     gui: Gui,
     player: Player,
@@ -54,6 +69,18 @@ impl Game {
             sky.bind_group_layout(),
             textures.bind_group_layout(),
         );
+        let entities = EntityLayer::new(
+            renderer,
+            player.bind_group_layout(),
+            sky.bind_group_layout(),
+            textures.bind_group_layout(),
+        );
+        let border = BorderWall::new(renderer, player.bind_group_layout());
+        let weather = WeatherLayer::new(
+            renderer,
+            player.bind_group_layout(),
+            sky.bind_group_layout(),
+        );
         let processor = PostProcessor::new(renderer, surface);
         let clouds = CloudLayer::new(
             renderer,
@@ -86,11 +113,16 @@ impl Game {
             textures.bind_group_layout(),
         );
         Self {
+            clock: Default::default(),
+            weather,
             sky,
             world,
+            entities,
+            border,
             clouds,
             fog,
             hover,
+            block_entities: Default::default(),
             aces,
             gui,
             player,
@@ -119,6 +151,30 @@ impl Game {
             self.depth.view(),
             &self.player.frustum(),
             |encoder| {
+                self.entities.draw(
+                    self.fog.view(),
+                    encoder,
+                    self.player.bind_group(),
+                    self.sky.bind_group(),
+                    self.textures.bind_group(),
+                    self.depth.view(),
+                );
+
+                self.border.draw(
+                    self.fog.view(),
+                    encoder,
+                    self.player.bind_group(),
+                    self.depth.view(),
+                );
+
+                self.weather.draw(
+                    self.fog.view(),
+                    encoder,
+                    self.player.bind_group(),
+                    self.sky.bind_group(),
+                    self.depth.view(),
+                );
+
                 self.fog.draw(
                     self.processor.view(),
                     encoder,
@@ -201,13 +257,18 @@ impl EventHandler for Game {
             is_surface_texture_lost,
         ): Self::Context<'_>,
     ) {
-        self.sky.handle(event, renderer);
+        self.clock.handle(event, dt);
+        self.weather.handle(event, (renderer, self.player.origin(), dt));
+        self.sky.handle(event, (renderer, surface, self.clock.time(), self.weather.state()));
         self.world.handle(event, renderer);
-        self.clouds.handle(event, dt);
+        self.entities.handle(event, (renderer, dt));
+        self.border.handle(event, renderer);
+        self.clouds.handle(event, (dt, self.clock.time(), self.weather.state()));
         self.fog.handle(event, (renderer, surface));
         self.hover.handle(event, ());
-        self.gui.handle(event, (renderer, surface));
-        self.player.handle(event, (client_tx, renderer, surface, &self.gui, dt));
+        self.block_entities.handle(event, &mut self.gui);
+        self.gui.handle(event, (client_tx, renderer, surface, dt));
+        self.player.handle(event, (client_tx, renderer, surface, &mut self.gui, dt));
         self.depth.handle(event, (renderer, surface));
         self.processor.handle(event, (renderer, surface));
 
@@ -239,6 +300,14 @@ struct BlockTextureArray(ImageTextureArray);
 
 impl BlockTextureArray {
     fn new(renderer: &Renderer, surface: &Surface) -> Self {
+        let max_count = renderer
+            .device
+            .limits()
+            .max_binding_array_elements_per_shader_stage;
+        assert!(
+            TEX_PATHS.len() <= max_count as usize,
+            "texture count must not exceed {max_count}, the binding array limit of the adapter",
+        );
         Self(
             ImageTextureArray::builder()
                 .renderer(renderer)
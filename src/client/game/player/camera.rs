@@ -10,11 +10,12 @@ use bitflags::bitflags;
 use nalgebra::{Matrix4, Point3, Vector3, matrix, vector};
 use std::{
     f32::consts::{FRAC_PI_2, TAU},
+    mem,
     time::Duration,
 };
 use winit::{
     event::{ButtonSource, DeviceEvent, ElementState, KeyEvent, MouseButton, WindowEvent},
-    keyboard::{KeyCode, PhysicalKey},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
 };
 
 pub struct View {
@@ -107,6 +108,8 @@ pub struct Controller {
     key_history: Keys,
     relevant_buttons: MouseButtons,
     button_history: MouseButtons,
+    edits: Changes,
+    modifiers: ModifiersState,
     speed: f32,
     sensitivity: f32,
     pub applied_external_updates: bool,
@@ -138,11 +141,24 @@ impl Controller {
         }
 
         if self.relevant_buttons.contains(MouseButtons::RIGHT) {
-            changes.insert(Changes::BLOCK_PLACED);
+            changes.insert(if self.modifiers.shift_key() {
+                Changes::BLOCK_PLACED
+            } else {
+                Changes::BLOCK_USED
+            });
         } else if self.relevant_buttons.contains(MouseButtons::LEFT) {
             changes.insert(Changes::BLOCK_DESTROYED);
         }
 
+        if self.relevant_buttons.contains(MouseButtons::MIDDLE) {
+            changes.insert(Changes::BLOCK_PICKED);
+        }
+
+        self.relevant_buttons.remove(MouseButtons::MIDDLE);
+        self.button_history.remove(MouseButtons::MIDDLE);
+
+        changes.insert(mem::take(&mut self.edits));
+
         if !CLIENT_CONFIG.player.features.contains(PlayerFeatures::DRAWING_MODE) {
             let block_action_buttons = MouseButtons::LEFT | MouseButtons::RIGHT;
             self.relevant_buttons.remove(block_action_buttons);
@@ -210,7 +226,16 @@ impl EventHandler for Controller {
                         },
                     ..
                 } => {
+                    let is_ctrl_pressed = self.modifiers.control_key();
                     let (key, opp) = match keycode {
+                        KeyCode::KeyZ if is_ctrl_pressed && state.is_pressed() => {
+                            self.edits.insert(Changes::UNDONE);
+                            return;
+                        }
+                        KeyCode::KeyY if is_ctrl_pressed && state.is_pressed() => {
+                            self.edits.insert(Changes::REDONE);
+                            return;
+                        }
                         KeyCode::KeyW => (Keys::W, Keys::S),
                         KeyCode::KeyA => (Keys::A, Keys::D),
                         KeyCode::KeyS => (Keys::S, Keys::W),
@@ -235,6 +260,9 @@ impl EventHandler for Controller {
                         }
                     }
                 }
+                WindowEvent::ModifiersChanged(modifiers) => {
+                    self.modifiers = modifiers.state();
+                }
                 WindowEvent::PointerButton {
                     button: ButtonSource::Mouse(button),
                     state,
@@ -243,6 +271,7 @@ impl EventHandler for Controller {
                     let (button, opp) = match button {
                         MouseButton::Left => (MouseButtons::LEFT, MouseButtons::RIGHT),
                         MouseButton::Right => (MouseButtons::RIGHT, MouseButtons::LEFT),
+                        MouseButton::Middle => (MouseButtons::MIDDLE, MouseButtons::empty()),
                         _ => return,
                     };
 
@@ -269,11 +298,16 @@ impl EventHandler for Controller {
 }
 
 bitflags! {
+    #[derive(Default)]
     pub struct Changes: u8 {
         const MOVED = 1 << 0;
         const ROTATED = 1 << 1;
         const BLOCK_PLACED = 1 << 2;
         const BLOCK_DESTROYED = 1 << 3;
+        const BLOCK_PICKED = 1 << 4;
+        const UNDONE = 1 << 5;
+        const REDONE = 1 << 6;
+        const BLOCK_USED = 1 << 7;
         const VIEW = Self::MOVED.bits() | Self::ROTATED.bits();
     }
 
@@ -291,5 +325,6 @@ bitflags! {
     struct MouseButtons: u8 {
         const LEFT = 1 << 0;
         const RIGHT = 1 << 1;
+        const MIDDLE = 1 << 2;
     }
 }
@@ -8,7 +8,10 @@ use crate::{
         event_loop::{Event, EventHandler},
         renderer::{Renderer, Surface, buffer::MemoryState, uniform::Uniform},
     },
-    server::{ServerEvent, game::world::chunk::Chunk},
+    server::{
+        ServerEvent,
+        game::world::{block::Block, border::WorldBorder, chunk::Chunk},
+    },
     shared::color::Float3,
 };
 use bitflags::bitflags;
@@ -26,6 +29,8 @@ pub struct Player {
     projection: Projection,
     controller: Controller,
     uniform: Uniform<PlayerUniformData>,
+    hovered_block: Option<Block>,
+    border: Option<WorldBorder>,
 }
 
 impl Player {
@@ -44,6 +49,8 @@ impl Player {
             projection,
             controller,
             uniform,
+            hovered_block: None,
+            border: None,
         }
     }
 
@@ -55,6 +62,10 @@ impl Player {
         self.uniform.bind_group()
     }
 
+    pub fn origin(&self) -> Point3<f32> {
+        self.view.origin
+    }
+
     pub fn frustum(&self) -> Frustum {
         Frustum::new(
             self.view.origin,
@@ -74,7 +85,7 @@ impl EventHandler for Player {
         &'a Sender<ClientEvent>,
         &'a Renderer,
         &'a Surface,
-        &'a Gui,
+        &'a mut Gui,
         Duration,
     );
 
@@ -83,7 +94,9 @@ impl EventHandler for Player {
         event: &Event,
         (client_tx, renderer, surface, gui, dt): Self::Context<'_>,
     ) {
-        self.controller.handle(event, ());
+        if !gui.is_typing(event) {
+            self.controller.handle(event, ());
+        }
 
         match event {
             Event::Resumed => {
@@ -95,8 +108,23 @@ impl EventHandler for Player {
                 self.view = View::new(origin, dir);
                 self.controller.applied_external_updates = true;
             }
+            &Event::ServerEvent(ServerEvent::PlayerTeleported { origin }) => {
+                self.view.origin = origin;
+                self.controller.applied_external_updates = true;
+                _ = client_tx.send(ClientEvent::PlayerPositionChanged { origin });
+            }
+            &Event::ServerEvent(ServerEvent::WorldInitialized { border, .. }) => {
+                self.border = Some(border);
+                self.hovered_block = None;
+            }
+            Event::ServerEvent(ServerEvent::BlockHovered(data)) => {
+                self.hovered_block = data.map(|data| data.block);
+            }
             Event::WindowEvent(WindowEvent::RedrawRequested) => {
                 let changes = self.controller.apply_updates(&mut self.view, dt);
+                if let Some(border) = self.border {
+                    self.view.origin = border.clamp(self.view.origin);
+                }
 
                 if changes.contains(Changes::MOVED) {
                     _ = client_tx.send(ClientEvent::PlayerPositionChanged {
@@ -114,7 +142,11 @@ impl EventHandler for Player {
                     self.projection.aspect = surface.width() / surface.height();
                 }
 
-                if changes.contains(Changes::BLOCK_PLACED) {
+                if changes.contains(Changes::BLOCK_USED)
+                    && self.hovered_block.is_some_and(Block::is_usable)
+                {
+                    _ = client_tx.send(ClientEvent::BlockUsed(gui.selected_block()));
+                } else if changes.intersects(Changes::BLOCK_PLACED | Changes::BLOCK_USED) {
                     if let Some(block) = gui.selected_block() {
                         _ = client_tx.send(ClientEvent::BlockPlaced(block));
                     }
@@ -122,6 +154,20 @@ impl EventHandler for Player {
                     _ = client_tx.send(ClientEvent::BlockDestroyed);
                 }
 
+                if changes.contains(Changes::UNDONE) {
+                    _ = client_tx.send(ClientEvent::EditUndone);
+                }
+
+                if changes.contains(Changes::REDONE) {
+                    _ = client_tx.send(ClientEvent::EditRedone);
+                }
+
+                if changes.contains(Changes::BLOCK_PICKED)
+                    && let Some(block) = self.hovered_block
+                {
+                    gui.pick_block(block);
+                }
+
                 let applied_external_updates =
                     mem::take(&mut self.controller.applied_external_updates);
 
@@ -8,7 +8,10 @@ use crate::{
         event_loop::{Event, EventHandler},
         renderer::{Renderer, Surface, buffer::MemoryState, uniform::Uniform},
     },
-    server::{ServerEvent, game::clock::Time},
+    server::{
+        ServerEvent,
+        game::{clock::Time, weather::WeatherState, world::block::data::join},
+    },
     shared::{
         color::{Float3, Rgb},
         utils,
@@ -20,14 +23,17 @@ use nalgebra::Vector3;
 use object::{ObjectConfig, ObjectSet};
 use serde::Deserialize;
 use star::{StarConfig, StarDome};
+use std::{collections::BTreeMap, ops::Deref};
 use winit::event::WindowEvent;
 
 pub struct Sky {
+    config: &'static SkyConfig,
+    player_bind_group_layout: wgpu::BindGroupLayout,
     atmosphere: Atmosphere,
     stars: StarDome,
     objects: ObjectSet,
     uniform: Uniform<SkyUniformData>,
-    updated_time: Option<Time>,
+    time: Option<(Time, WeatherState)>,
 }
 
 impl Sky {
@@ -46,19 +52,23 @@ impl Sky {
             player_bind_group_layout,
             uniform.bind_group_layout(),
         );
-        let stars = StarDome::new(renderer, player_bind_group_layout);
+        let config = CLIENT_CONFIG.sky.get(SkyProfiles::DEFAULT);
+        let stars = StarDome::new(renderer, &config.star, player_bind_group_layout);
         let objects = ObjectSet::new(
             renderer,
             surface,
+            &config.object,
             player_bind_group_layout,
             uniform.bind_group_layout(),
         );
         Self {
+            config,
+            player_bind_group_layout: player_bind_group_layout.clone(),
             atmosphere,
             stars,
             objects,
             uniform,
-            updated_time: Some(Default::default()),
+            time: None,
         }
     }
 
@@ -103,22 +113,29 @@ impl Sky {
 }
 
 impl EventHandler for Sky {
-    type Context<'a> = &'a Renderer;
-
-    fn handle(&mut self, event: &Event, renderer: Self::Context<'_>) {
-        self.stars.handle(event, renderer);
-        self.objects.handle(event, ());
-
-        match *event {
-            Event::ServerEvent(ServerEvent::TimeUpdated(time)) => {
-                self.updated_time = Some(time);
-            }
-            Event::WindowEvent(WindowEvent::RedrawRequested) => {
-                if let Some(time) = self.updated_time.take() {
-                    self.uniform.set(renderer, &CLIENT_CONFIG.sky.data(time));
-                }
-            }
-            _ => {}
+    type Context<'a> = (&'a Renderer, &'a Surface, Time, WeatherState);
+
+    fn handle(&mut self, event: &Event, (renderer, surface, time, weather): Self::Context<'_>) {
+        if let Event::ServerEvent(ServerEvent::WorldInitialized { sky, .. }) = event {
+            self.config = CLIENT_CONFIG.sky.get(sky);
+            self.stars = StarDome::new(renderer, &self.config.star, &self.player_bind_group_layout);
+            self.objects = ObjectSet::new(
+                renderer,
+                surface,
+                &self.config.object,
+                &self.player_bind_group_layout,
+                self.uniform.bind_group_layout(),
+            );
+            self.time = None;
+        }
+
+        self.stars.handle(event, time);
+        self.objects.handle(event, time);
+
+        if let Event::WindowEvent(WindowEvent::RedrawRequested) = event
+            && self.time.replace((time, weather)) != Some((time, weather))
+        {
+            self.uniform.set(renderer, &self.config.data(time, weather));
         }
     }
 }
@@ -190,6 +207,22 @@ impl Glow {
     }
 }
 
+#[derive(Deserialize)]
+pub struct SkyProfiles(BTreeMap<String, SkyConfig>);
+
+impl SkyProfiles {
+    const DEFAULT: &str = "default";
+
+    fn get(&self, name: &str) -> &SkyConfig {
+        self.0.get(name).unwrap_or_else(|| {
+            panic!(
+                "invalid sky \"{name}\", expected one of [\"{}\"]",
+                join(self.0.keys().map(Deref::deref)),
+            )
+        })
+    }
+}
+
 #[derive(Deserialize)]
 pub struct SkyConfig {
     sun_intensity: f32,
@@ -201,19 +234,20 @@ pub struct SkyConfig {
 }
 
 impl SkyConfig {
-    fn data(&self, time: Time) -> SkyUniformData {
+    fn data(&self, time: Time, weather: WeatherState) -> SkyUniformData {
         let progress = time.stage().progress();
+        let light_factor = weather.blend(|kind| CLIENT_CONFIG.weather.kinds[kind].light_intensity);
         SkyUniformData::new(
             time.sky_rotation() * Vector3::x(),
             utils::lerp(self.day.color, self.night.color, progress),
             utils::lerp(self.day.horizon_color, self.night.horizon_color, progress),
             Glow::new(self.glow.color(progress), progress),
-            utils::lerp(self.sun_intensity, 1.0, progress),
+            utils::lerp(self.sun_intensity, 1.0, progress) * light_factor,
             utils::lerp(
                 self.day.light_intensity,
                 self.night.light_intensity,
                 progress,
-            ),
+            ) * light_factor,
         )
     }
 }
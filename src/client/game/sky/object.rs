@@ -1,6 +1,5 @@
 use crate::{
     client::{
-        CLIENT_CONFIG,
         event_loop::{Event, EventHandler},
         renderer::{
             Renderer, Surface,
@@ -10,33 +9,47 @@ use crate::{
             utils::{Immediates, load_rgba, read_wgsl},
         },
     },
-    server::{ServerEvent, game::clock::Time},
+    server::game::clock::Time,
 };
 use bytemuck::{Pod, Zeroable};
-use nalgebra::{Matrix4, Point3, Vector3, vector};
+use nalgebra::{Matrix4, Point3, UnitQuaternion, Vector3, vector};
 use serde::Deserialize;
+use std::num::NonZeroU32;
+use winit::event::WindowEvent;
 
 pub struct ObjectSet {
+    bodies: &'static [BodyConfig],
     textures: ImageTextureArray,
     program: Program,
-    sun_imm: ObjectImmediates,
-    moon_imm: ObjectImmediates,
+    imms: Vec<ObjectImmediates>,
 }
 
 impl ObjectSet {
     pub fn new(
         renderer: &Renderer,
         surface: &Surface,
+        config: &'static ObjectConfig,
         player_bind_group_layout: &wgpu::BindGroupLayout,
         sky_bind_group_layout: &wgpu::BindGroupLayout,
     ) -> Self {
+        let bodies = &*config.bodies;
+        for body in bodies {
+            let period = body.orbit.period;
+            if !(period.is_finite() && period > 0.0) {
+                panic!(
+                    "invalid orbit period {period} of sky body \"{}\", expected a positive number",
+                    body.texture
+                );
+            }
+        }
         let textures = ImageTextureArray::builder()
             .renderer(renderer)
             .surface(surface)
-            .images([
-                load_rgba("assets/textures/sky/sun.png"),
-                load_rgba("assets/textures/sky/moon.png"),
-            ])
+            .images(
+                bodies
+                    .iter()
+                    .map(|body| load_rgba(format!("assets/textures/sky/{}", body.texture))),
+            )
             .is_srgb(true)
             .build();
         let program = Program::builder()
@@ -50,12 +63,11 @@ impl ObjectSet {
             .immediate_size(ObjectImmediates::SIZE)
             .format(PostProcessor::FORMAT)
             .build();
-        let (sun_imm, moon_imm) = Self::imm(Default::default());
         Self {
+            bodies,
             textures,
             program,
-            sun_imm,
-            moon_imm,
+            imms: Self::imms(bodies, Default::default()).collect(),
         }
     }
 
@@ -73,28 +85,34 @@ impl ObjectSet {
                 self.textures.bind_group(),
             ],
         );
-        self.sun_imm.set(render_pass);
-        render_pass.draw(0..6, 0..1);
-        self.moon_imm.set(render_pass);
-        render_pass.draw(0..6, 0..1);
+        for imm in &self.imms {
+            imm.set(render_pass);
+            render_pass.draw(0..6, 0..1);
+        }
     }
 
-    fn imm(time: Time) -> (ObjectImmediates, ObjectImmediates) {
-        let sun_dir = time.sky_rotation() * Vector3::x();
+    fn imms(bodies: &[BodyConfig], time: Time) -> impl Iterator<Item = ObjectImmediates> {
         let is_am = time.is_am();
-        (
-            ObjectImmediates::new(sun_dir, 0, is_am),
-            ObjectImmediates::new(-sun_dir, 1, is_am),
-        )
+        (0..).zip(bodies).map(move |(i, body)| {
+            let rotation = body.orbit.rotation(time);
+            ObjectImmediates::new(
+                rotation * Vector3::x(),
+                i,
+                body.phase(time),
+                body.size,
+                is_am,
+            )
+        })
     }
 }
 
 impl EventHandler for ObjectSet {
-    type Context<'a> = ();
+    type Context<'a> = Time;
 
-    fn handle(&mut self, event: &Event, (): Self::Context<'_>) {
-        if let Event::ServerEvent(ServerEvent::TimeUpdated(time)) = *event {
-            (self.sun_imm, self.moon_imm) = Self::imm(time);
+    fn handle(&mut self, event: &Event, time: Self::Context<'_>) {
+        if let Event::WindowEvent(WindowEvent::RedrawRequested) = event {
+            self.imms.clear();
+            self.imms.extend(Self::imms(self.bodies, time));
         }
     }
 }
@@ -104,15 +122,18 @@ impl EventHandler for ObjectSet {
 struct ObjectImmediates {
     m: Matrix4<f32>,
     tex_index: u32,
+    phase: f32,
+    padding: [f32; 2],
 }
 
 impl ObjectImmediates {
-    fn new(dir: Vector3<f32>, tex_index: u32, is_am: bool) -> Self {
-        let size = CLIENT_CONFIG.sky.object.size;
+    fn new(dir: Vector3<f32>, tex_index: u32, phase: f32, size: f32, is_am: bool) -> Self {
         Self {
             m: Matrix4::face_towards(&dir.into(), &Point3::origin(), &Self::up(is_am))
                 .prepend_nonuniform_scaling(&vector![size, size, 1.0]),
             tex_index,
+            phase,
+            padding: Default::default(),
         }
     }
 
@@ -125,5 +146,57 @@ impl Immediates for ObjectImmediates {}
 
 #[derive(Deserialize)]
 pub struct ObjectConfig {
+    bodies: Vec<BodyConfig>,
+}
+
+#[derive(Deserialize)]
+struct BodyConfig {
+    texture: String,
     size: f32,
+    #[serde(default)]
+    orbit: OrbitConfig,
+    phases: Option<PhaseConfig>,
+}
+
+impl BodyConfig {
+    const FULL: f32 = 0.5;
+
+    fn phase(&self, time: Time) -> f32 {
+        self.phases.as_ref().map_or(Self::FULL, |phases| {
+            let phase = (time.day() + phases.offset as u64) % phases.count.get() as u64;
+            phase as f32 / phases.count.get() as f32
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct OrbitConfig {
+    period: f32,
+    offset: f32,
+    tilt: f32,
+}
+
+impl OrbitConfig {
+    fn rotation(&self, time: Time) -> UnitQuaternion<f32> {
+        UnitQuaternion::new(Vector3::x() * self.tilt.to_radians())
+            * time.orbit_rotation(self.period, self.offset)
+    }
+}
+
+impl Default for OrbitConfig {
+    fn default() -> Self {
+        Self {
+            period: 1.0,
+            offset: 0.0,
+            tilt: 0.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PhaseConfig {
+    count: NonZeroU32,
+    #[serde(default)]
+    offset: u32,
 }
@@ -1,6 +1,5 @@
 use crate::{
     client::{
-        CLIENT_CONFIG,
         event_loop::{Event, EventHandler},
         renderer::{
             Renderer,
@@ -10,13 +9,10 @@ use crate::{
             utils::{Immediates, Vertex, read_wgsl},
         },
     },
-    server::{
-        ServerEvent,
-        game::clock::{Stage, Time},
-    },
+    server::game::clock::Time,
 };
 use bytemuck::{Pod, Zeroable};
-use nalgebra::{Matrix4, Point3, UnitQuaternion, Vector3, point, vector};
+use nalgebra::{Matrix4, Point3, Vector3, point, vector};
 use rand::{
     Rng, SeedableRng,
     distr::{Distribution, Uniform},
@@ -27,22 +23,26 @@ use std::f32::consts::{FRAC_PI_2, PI};
 use winit::event::WindowEvent;
 
 pub struct StarDome {
-    stars: Box<[Star]>,
+    config: &'static StarConfig,
     instance_buffer: VertexBuffer<StarInstance>,
     program: Program,
     imm: StarImmediates,
-    updated_rotation: Option<UnitQuaternion<f32>>,
 }
 
 impl StarDome {
-    pub fn new(renderer: &Renderer, player_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
-        let count = CLIENT_CONFIG.sky.star.count;
-        let stars = {
+    pub fn new(
+        renderer: &Renderer,
+        config: &'static StarConfig,
+        player_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let instances = {
             let mut rng = SmallRng::seed_from_u64(8008);
             let generator = StarGenerator::default();
-            (0..count).map(|_| generator.generate(&mut rng)).collect()
+            (0..config.count)
+                .map(|_| StarInstance::new(generator.generate(&mut rng), config.size))
+                .collect::<Vec<_>>()
         };
-        let instance_buffer = VertexBuffer::new(renderer, MemoryState::Uninit(count));
+        let instance_buffer = VertexBuffer::new(renderer, MemoryState::Immutable(&instances));
         let program = Program::builder()
             .renderer(renderer)
             .shader_desc(read_wgsl("assets/shaders/star.wgsl"))
@@ -53,11 +53,10 @@ impl StarDome {
             .blend(wgpu::BlendState::ALPHA_BLENDING)
             .build();
         Self {
-            stars,
+            config,
             instance_buffer,
             program,
-            imm: Default::default(),
-            updated_rotation: Some(Time::default().sky_rotation()),
+            imm: StarImmediates::new(Default::default(), config.brightness),
         }
     }
 
@@ -69,30 +68,14 @@ impl StarDome {
             render_pass.draw(0..6, 0..self.instance_buffer.len());
         }
     }
-
-    fn instances(&self, rotation: UnitQuaternion<f32>) -> impl Iterator<Item = StarInstance> {
-        self.stars
-            .iter()
-            .map(move |&star| StarInstance::new(star, rotation))
-    }
 }
 
 impl EventHandler for StarDome {
-    type Context<'a> = &'a Renderer;
-
-    fn handle(&mut self, event: &Event, renderer: Self::Context<'_>) {
-        match event {
-            Event::ServerEvent(ServerEvent::TimeUpdated(time)) => {
-                self.imm = StarImmediates::new(time.stage());
-                self.updated_rotation = Some(time.sky_rotation());
-            }
-            Event::WindowEvent(WindowEvent::RedrawRequested) => {
-                if let Some(rotation) = self.updated_rotation.take() {
-                    let instances = self.instances(rotation).collect::<Vec<_>>();
-                    self.instance_buffer.write(renderer, &instances);
-                }
-            }
-            _ => {}
+    type Context<'a> = Time;
+
+    fn handle(&mut self, event: &Event, time: Self::Context<'_>) {
+        if let Event::WindowEvent(WindowEvent::RedrawRequested) = event {
+            self.imm = StarImmediates::new(time, self.config.brightness);
         }
     }
 }
@@ -145,10 +128,9 @@ struct StarInstance {
 }
 
 impl StarInstance {
-    fn new(Star { coords, rotation }: Star, sky_rotation: UnitQuaternion<f32>) -> Self {
-        let size = CLIENT_CONFIG.sky.star.size;
+    fn new(Star { coords, rotation }: Star, size: f32) -> Self {
         Self {
-            m: Matrix4::face_towards(&(sky_rotation * coords), &Point3::origin(), &Vector3::y())
+            m: Matrix4::face_towards(&coords, &Point3::origin(), &Vector3::y())
                 * Matrix4::new_rotation(Vector3::z() * rotation)
                     .prepend_nonuniform_scaling(&vector![size, size, 1.0]),
         }
@@ -164,24 +146,21 @@ impl Vertex for StarInstance {
 #[repr(C)]
 #[derive(Clone, Copy, Zeroable, Pod)]
 struct StarImmediates {
+    sky: Matrix4<f32>,
     opacity: f32,
+    padding: [f32; 3],
 }
 
 impl StarImmediates {
-    fn new(stage: Stage) -> Self {
-        let brightness = CLIENT_CONFIG.sky.star.brightness;
+    fn new(time: Time, brightness: f32) -> Self {
         Self {
-            opacity: stage.lerp(-brightness / 2.0, brightness).max(0.0),
+            sky: time.sky_rotation().to_homogeneous(),
+            opacity: time.stage().lerp(-brightness / 2.0, brightness).max(0.0),
+            padding: Default::default(),
         }
     }
 }
 
-impl Default for StarImmediates {
-    fn default() -> Self {
-        Self::new(Default::default())
-    }
-}
-
 impl Immediates for StarImmediates {}
 
 #[derive(Deserialize)]
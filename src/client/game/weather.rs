@@ -0,0 +1,303 @@
+use crate::{
+    client::{
+        CLIENT_CONFIG,
+        event_loop::{Event, EventHandler},
+        renderer::{
+            Renderer,
+            buffer::{MemoryState, VertexBuffer},
+            effect::PostProcessor,
+            program::Program,
+            texture::screen::DepthBuffer,
+            utils::{Vertex, read_wgsl},
+        },
+    },
+    server::{
+        ServerEvent,
+        game::{
+            weather::{WeatherKind, WeatherState},
+            world::chunk::Chunk,
+        },
+    },
+    shared::{
+        color::{Rgb, Rgba},
+        enum_map::EnumMap,
+        utils,
+    },
+};
+use bytemuck::{Pod, Zeroable};
+use nalgebra::{Point2, Point3, Vector2, Vector3, point, vector};
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::{f32::consts::TAU, ops::Range, time::Duration};
+use winit::event::WindowEvent;
+
+pub struct WeatherLayer {
+    state: WeatherState,
+    y_range: Range<i32>,
+    floors: FxHashMap<Point3<i32>, [[u8; Chunk::DIM]; Chunk::DIM]>,
+    particles: Vec<Particle>,
+    instance_buffer: VertexBuffer<ParticleInstance>,
+    program: Program,
+    rng: SmallRng,
+}
+
+impl WeatherLayer {
+    pub fn new(
+        renderer: &Renderer,
+        player_bind_group_layout: &wgpu::BindGroupLayout,
+        sky_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let instance_buffer = VertexBuffer::new(
+            renderer,
+            MemoryState::Uninit(CLIENT_CONFIG.weather.max_particles),
+        );
+        let program = Program::builder()
+            .renderer(renderer)
+            .shader_desc(read_wgsl("assets/shaders/weather.wgsl"))
+            .bind_group_layouts(&[player_bind_group_layout, sky_bind_group_layout])
+            .buffers(&[ParticleInstance::desc()])
+            .depth_stencil(wgpu::DepthStencilState {
+                format: DepthBuffer::FORMAT,
+                depth_write_enabled: Some(false),
+                depth_compare: Some(wgpu::CompareFunction::Less),
+                stencil: Default::default(),
+                bias: Default::default(),
+            })
+            .format(PostProcessor::FORMAT)
+            .blend(wgpu::BlendState::ALPHA_BLENDING)
+            .build();
+        Self {
+            state: Default::default(),
+            y_range: 0..0,
+            floors: Default::default(),
+            particles: vec![],
+            instance_buffer,
+            program,
+            rng: SmallRng::seed_from_u64(0),
+        }
+    }
+
+    pub fn state(&self) -> WeatherState {
+        self.state
+    }
+
+    pub fn draw(
+        &self,
+        view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+        player_bind_group: &wgpu::BindGroup,
+        sky_bind_group: &wgpu::BindGroup,
+        depth_view: &wgpu::TextureView,
+    ) {
+        if self.particles.is_empty() {
+            return;
+        }
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+
+        self.program
+            .bind(&mut render_pass, [player_bind_group, sky_bind_group]);
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        render_pass.draw(0..6, 0..self.particles.len() as u32);
+    }
+
+    fn floor(&self, xz: Point2<i64>) -> f32 {
+        let chunk_xz = utils::chunk_coords(xz);
+        let block_xz = utils::block_coords(xz);
+        self.y_range
+            .clone()
+            .rev()
+            .find_map(|y| {
+                let floors = self.floors.get(&point![chunk_xz.x, y, chunk_xz.y])?;
+                let floor = floors[block_xz.x as usize][block_xz.y as usize];
+                (floor != 0).then(|| (y as i64 * Chunk::DIM as i64 + floor as i64) as f32)
+            })
+            .unwrap_or((self.y_range.start as i64 * Chunk::DIM as i64) as f32)
+    }
+
+    fn spawn(&mut self, center: Point3<f32>) -> Option<Particle> {
+        let WeatherConfig { radius, height, .. } = CLIENT_CONFIG.weather;
+        let kind = self.state.pick(self.rng.random());
+        CLIENT_CONFIG.weather.kinds[kind].precipitation.as_ref()?;
+
+        let theta = self.rng.random_range(0.0..TAU);
+        let distance = radius * self.rng.random::<f32>().sqrt();
+        let xz = center.xz() + vector![theta.cos(), theta.sin()] * distance;
+        let floor = self.floor(xz.map(|c| c.floor() as i64));
+        let bottom = floor.max(center.y - height);
+        let top = center.y + height;
+        (bottom < top).then(|| Particle {
+            origin: point![xz.x, self.rng.random_range(bottom..top), xz.y],
+            floor,
+            kind,
+            phase: self.rng.random_range(0.0..TAU),
+        })
+    }
+
+    fn update(&mut self, center: Point3<f32>, dt: Duration) {
+        let WeatherConfig {
+            radius,
+            height,
+            max_particles,
+            ref kinds,
+        } = CLIENT_CONFIG.weather;
+        let density = self.state.blend(|kind| {
+            kinds[kind]
+                .precipitation
+                .as_ref()
+                .map_or(0.0, |precipitation| precipitation.density)
+        });
+        let target = ((density * max_particles as f32) as usize).min(max_particles);
+        let dt = dt.as_secs_f32();
+
+        self.particles.truncate(target);
+        let mut particles = vec![];
+
+        for mut particle in self.particles.drain(..) {
+            if let Some(precipitation) = &kinds[particle.kind].precipitation {
+                particle.phase = (particle.phase + dt) % TAU;
+                particle.origin.y -= precipitation.fall_speed * dt;
+                particle.origin.x += particle.phase.sin() * precipitation.drift * dt;
+            }
+
+            let is_inside = (particle.origin.xz() - center.xz()).norm() <= radius
+                && particle.origin.y <= center.y + height;
+            if is_inside && particle.origin.y >= particle.floor.max(center.y - height) {
+                particles.push(particle);
+            }
+        }
+
+        while particles.len() < target {
+            match self.spawn(center) {
+                Some(particle) => particles.push(particle),
+                None => break,
+            }
+        }
+
+        self.particles = particles;
+    }
+}
+
+impl EventHandler for WeatherLayer {
+    type Context<'a> = (&'a Renderer, Point3<f32>, Duration);
+
+    fn handle(&mut self, event: &Event, (renderer, origin, dt): Self::Context<'_>) {
+        match event {
+            &Event::ServerEvent(ServerEvent::WeatherUpdated(state)) => {
+                self.state = state;
+            }
+            Event::ServerEvent(ServerEvent::WorldInitialized { y_range, .. }) => {
+                self.y_range = y_range.clone();
+                self.floors.clear();
+                self.particles.clear();
+            }
+            Event::ServerEvent(
+                ServerEvent::ChunkLoaded { coords, data, .. }
+                | ServerEvent::ChunkUpdated { coords, data, .. },
+            ) => {
+                self.floors.insert(*coords, data.sky_floors());
+            }
+            Event::ServerEvent(ServerEvent::ChunkUnloaded { coords, .. }) => {
+                self.floors.remove(coords);
+            }
+            Event::WindowEvent(WindowEvent::RedrawRequested) => {
+                self.update(origin, dt);
+                if !self.particles.is_empty() {
+                    let instances = self
+                        .particles
+                        .iter()
+                        .map(ParticleInstance::new)
+                        .collect::<Vec<_>>();
+                    self.instance_buffer.write(renderer, &instances);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Particle {
+    origin: Point3<f32>,
+    floor: f32,
+    kind: WeatherKind,
+    phase: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod)]
+struct ParticleInstance {
+    origin: Vector3<f32>,
+    opacity: f32,
+    color: Rgb<f32>,
+    size: Vector2<f32>,
+}
+
+impl ParticleInstance {
+    fn new(particle: &Particle) -> Self {
+        let precipitation = CLIENT_CONFIG.weather.kinds[particle.kind]
+            .precipitation
+            .as_ref()
+            .unwrap_or_else(|| unreachable!());
+        Self {
+            origin: particle.origin.coords,
+            opacity: precipitation.color.a,
+            color: precipitation.color.rgb,
+            size: precipitation.size,
+        }
+    }
+}
+
+impl Vertex for ParticleInstance {
+    const STEP_MODE: wgpu::VertexStepMode = wgpu::VertexStepMode::Instance;
+    const ATTRIBS: &[wgpu::VertexAttribute] = &wgpu::vertex_attr_array![
+        0 => Float32x3,
+        1 => Float32,
+        2 => Float32x3,
+        3 => Float32x2,
+    ];
+}
+
+#[derive(Deserialize)]
+pub struct WeatherConfig {
+    radius: f32,
+    height: f32,
+    max_particles: usize,
+    pub kinds: EnumMap<WeatherKind, WeatherKindConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct WeatherKindConfig {
+    pub light_intensity: f32,
+    pub cloud_color: Rgb<f32>,
+    pub cloud_opacity: f32,
+    precipitation: Option<PrecipitationConfig>,
+}
+
+#[derive(Deserialize)]
+struct PrecipitationConfig {
+    density: f32,
+    fall_speed: f32,
+    drift: f32,
+    size: Vector2<f32>,
+    color: Rgba<f32>,
+}
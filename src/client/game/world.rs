@@ -34,6 +34,7 @@ pub struct World {
     program: Program,
     unloaded: FxHashSet<Point3<i32>>,
     groups: FxHashMap<Uuid, Vec<Result<ChunkOutput, Point3<i32>>>>,
+    reset_at: Instant,
     group_workers: ThreadPool<(ChunkInput, GroupId), (ChunkOutput, GroupId)>,
     workers: ThreadPool<ChunkInput, ChunkOutput>,
 }
@@ -74,6 +75,7 @@ impl World {
                 .build(),
             unloaded: Default::default(),
             groups: Default::default(),
+            reset_at: Instant::now(),
             group_workers: ThreadPool::new(|(input, group_id)| (Self::vertices(input), group_id)),
             workers: ThreadPool::new(Self::vertices),
         }
@@ -157,6 +159,12 @@ impl World {
         output: Result<ChunkOutput, Point3<i32>>,
         group_id: Option<GroupId>,
     ) {
+        if let Ok((.., updated_at)) = &output
+            && *updated_at < self.reset_at
+        {
+            return;
+        }
+
         let Some(GroupId {
             id: group_id,
             size: group_size,
@@ -283,6 +291,12 @@ impl EventHandler for World {
                 } => {
                     self.send((*coords, data.clone(), Instant::now()), *group_id);
                 }
+                ServerEvent::WorldInitialized { .. } => {
+                    self.meshes.clear();
+                    self.unloaded.clear();
+                    self.groups.clear();
+                    self.reset_at = Instant::now();
+                }
                 _ => {}
             },
             Event::WindowEvent(WindowEvent::RedrawRequested) => {
@@ -362,33 +376,46 @@ pub struct BlockVertex {
 }
 
 impl BlockVertex {
+    pub const MAX_TEX_COUNT: usize = 1 << 10;
+    const RADIX: u32 = Chunk::DIM as u32 + 1;
+
     pub fn new(
         coords: Point3<u8>,
-        tex_index: u8,
+        tex_index: u16,
         tex_coords: Point2<u8>,
         face: Face,
         ao: u8,
         light: BlockLight,
     ) -> Self {
         let mut data = [0; 2];
-        data[0].set_bit_range(4, 0, coords.x);
-        data[0].set_bit_range(9, 5, coords.y);
-        data[0].set_bit_range(14, 10, coords.z);
-        data[0].set_bit_range(22, 15, tex_index);
-        data[0].set_bit_range(31, 27, tex_coords.x);
-        data[1].set_bit_range(31, 27, tex_coords.y);
-        data[0].set_bit_range(24, 23, face as u8);
-        data[0].set_bit_range(26, 25, ao);
-        data[1].set_bit_range(26, 0, light.0);
+        data[0].set_bit_range(12, 0, Self::pack(coords.iter().copied()));
+        data[0].set_bit_range(21, 13, Self::pack(tex_coords.iter().copied()));
+        data[0].set_bit_range(31, 22, tex_index);
+        data[1].set_bit_range(23, 0, light.0);
+        data[1].set_bit_range(25, 24, face as u8);
+        data[1].set_bit_range(27, 26, ao);
         Self { data }
     }
 
-    fn coords(self) -> Point3<u8> {
+    pub fn with_lowering(mut self, lowering: u8) -> Self {
+        self.data[1].set_bit_range(31, 28, lowering);
+        self
+    }
+
+    pub fn coords(self) -> Point3<u8> {
+        let packed: u32 = self.data[0].bit_range(12, 0);
         point![
-            self.data[0].bit_range(4, 0),
-            self.data[0].bit_range(9, 5),
-            self.data[0].bit_range(14, 10),
+            packed % Self::RADIX,
+            packed / Self::RADIX % Self::RADIX,
+            packed / Self::RADIX.pow(2),
         ]
+        .map(|c| c as u8)
+    }
+
+    fn pack<I: DoubleEndedIterator<Item = u8>>(components: I) -> u32 {
+        components
+            .rev()
+            .fold(0, |packed, c| packed * Self::RADIX + c as u32)
     }
 }
 
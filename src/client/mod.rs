@@ -6,12 +6,18 @@ pub(crate) mod stopwatch;
 pub(crate) mod window;
 
 use crate::{
-    server::{ServerEvent, ServerSender, game::world::block::Block},
+    server::{
+        ServerEvent, ServerSender,
+        game::{recipe::CraftingGrid, world::block::Block},
+    },
     shared::toml,
 };
 use app::App;
 use crossbeam_channel::{Receiver, Sender};
-use game::{cloud::CloudConfig, gui::GuiConfig, player::PlayerConfig, sky::SkyConfig};
+use game::{
+    border::BorderConfig, cloud::CloudConfig, entity::EntityConfig, gui::GuiConfig,
+    player::PlayerConfig, sky::SkyProfiles, weather::WeatherConfig,
+};
 use nalgebra::{Point3, Vector3};
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
@@ -66,7 +72,12 @@ pub enum ClientEvent {
         dir: Vector3<f32>,
     },
     BlockPlaced(Block),
+    BlockUsed(Option<Block>),
     BlockDestroyed,
+    CraftRequested(CraftingGrid),
+    EditUndone,
+    EditRedone,
+    CommandSubmitted(String),
     #[serde(skip)]
     Connected(Box<ServerSender>),
     #[serde(skip)]
@@ -76,9 +87,12 @@ pub enum ClientEvent {
 #[derive(Deserialize)]
 struct ClientConfig {
     player: PlayerConfig,
-    sky: SkyConfig,
+    sky: SkyProfiles,
     cloud: CloudConfig,
     gui: GuiConfig,
+    weather: WeatherConfig,
+    border: BorderConfig,
+    entity: EntityConfig,
 }
 
 static CLIENT_CONFIG: LazyLock<ClientConfig> =
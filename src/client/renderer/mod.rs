@@ -47,7 +47,9 @@ impl Renderer {
                     | wgpu::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
                     | wgpu::Features::TEXTURE_BINDING_ARRAY,
                 required_limits: wgpu::Limits {
-                    max_binding_array_elements_per_shader_stage: 6,
+                    max_binding_array_elements_per_shader_stage: adapter
+                        .limits()
+                        .max_binding_array_elements_per_shader_stage,
                     max_immediate_size: 68,
                     ..Default::default()
                 },
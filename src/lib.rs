@@ -2,6 +2,7 @@
     impl_trait_in_assoc_type,
     int_roundings,
     maybe_uninit_fill,
+    result_option_map_or_default,
     trait_alias,
     type_alias_impl_trait
 )]
@@ -2,6 +2,7 @@ use super::{SERVER_CONFIG, ServerSender, ticker::Ticker};
 use crate::client::ClientEvent;
 use crossbeam_channel::{Receiver, RecvTimeoutError};
 use serde::Deserialize;
+use std::time::Duration;
 
 pub struct EventLoop {
     server_tx: ServerSender,
@@ -29,13 +30,20 @@ impl EventLoop {
                 }
                 Ok(event) => Event::Client(event),
                 Err(RecvTimeoutError::Timeout) => Event::Tick,
-                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    handler.handle(&Event::Disconnected, &self.server_tx);
+                    break;
+                }
             };
             handler.handle(&event, &self.server_tx);
         }
     }
 }
 
+pub fn tick_duration() -> Duration {
+    Duration::from_secs(1) / SERVER_CONFIG.event_loop.ticks_per_second
+}
+
 pub trait EventHandler<E> {
     type Context<'a>;
 
@@ -45,6 +53,7 @@ pub trait EventHandler<E> {
 pub enum Event {
     Client(ClientEvent),
     Tick,
+    Disconnected,
 }
 
 #[derive(Deserialize)]
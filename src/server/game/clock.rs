@@ -1,35 +1,107 @@
+use super::world::save::{Save, Saved};
 use crate::{
     client::ClientEvent,
     server::{
         SERVER_CONFIG, ServerEvent, ServerSender,
-        event_loop::{Event, EventHandler},
+        event_loop::{self, Event, EventHandler},
     },
     shared::utils::{self, Lerp},
 };
 use nalgebra::{UnitQuaternion, Vector3};
 use serde::{Deserialize, Serialize};
-use std::{f32::consts::TAU, ops::Range};
+use std::{f32::consts::TAU, num::NonZeroU64, ops::Range, time::Duration};
 
+#[derive(Serialize, Deserialize)]
 pub struct Clock {
+    day: u64,
     ticks: u16,
+    tick: u64,
+    progress: f32,
+    speed: f32,
+    is_paused: bool,
 }
 
 impl Clock {
-    fn send_time(&self, server_tx: &ServerSender) {
+    pub fn load(save_dir: &'static str) -> Saved<Self> {
+        Saved::load(save_dir, || Self {
+            day: 0,
+            ticks: SERVER_CONFIG.clock.starting_ticks(),
+            tick: 0,
+            progress: 0.0,
+            speed: 1.0,
+            is_paused: false,
+        })
+    }
+
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    pub fn ticks(&self) -> u16 {
+        self.ticks
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    pub fn is_speed_allowed(speed: f32) -> bool {
+        (0.0..=SERVER_CONFIG.clock.max_speed).contains(&speed)
+    }
+
+    pub fn set_ticks(&mut self, ticks: u16, server_tx: &ServerSender) {
+        self.ticks = ticks % SERVER_CONFIG.clock.ticks_per_day;
+        self.progress = 0.0;
+        self.send_time(server_tx);
+    }
+
+    pub fn set_stage(&mut self, stage: StageName, server_tx: &ServerSender) {
+        self.set_ticks(SERVER_CONFIG.clock.stage_ticks(stage), server_tx);
+    }
+
+    pub fn set_speed(&mut self, speed: f32, server_tx: &ServerSender) {
+        self.speed = speed;
+        self.send_time(server_tx);
+    }
+
+    pub fn set_paused(&mut self, is_paused: bool, server_tx: &ServerSender) {
+        self.is_paused = is_paused;
+        self.send_time(server_tx);
+    }
+
+    pub fn advance(&mut self) {
+        self.tick += 1;
+
+        if !self.is_paused && Self::is_speed_allowed(self.speed) {
+            self.progress += self.speed;
+            let steps = self.progress.floor();
+            self.progress -= steps;
+            let ticks_per_day = SERVER_CONFIG.clock.ticks_per_day as u64;
+            let ticks = (self.ticks as u64).saturating_add(steps as u64);
+            self.day = self.day.saturating_add(ticks / ticks_per_day);
+            self.ticks = (ticks % ticks_per_day) as u16;
+        }
+    }
+
+    pub fn send_time(&self, server_tx: &ServerSender) {
         _ = server_tx.send(ServerEvent::TimeUpdated(self.time()));
     }
 
     fn time(&self) -> Time {
-        Time { ticks: self.ticks }
+        Time {
+            day: self.day,
+            ticks: self.ticks as f32 + self.progress,
+            speed: if self.is_paused { 0.0 } else { self.speed },
+        }
     }
 }
 
-impl Default for Clock {
-    fn default() -> Self {
-        Self {
-            ticks: SERVER_CONFIG.clock.starting_ticks(),
-        }
-    }
+impl Save for Clock {
+    const NAME: &str = "clock";
 }
 
 impl EventHandler<Event> for Clock {
@@ -41,23 +113,51 @@ impl EventHandler<Event> for Clock {
                 self.send_time(server_tx);
             }
             Event::Tick => {
-                self.ticks = (self.ticks + 1) % SERVER_CONFIG.clock.ticks_per_day;
-                self.send_time(server_tx);
+                self.advance();
+                if self
+                    .tick
+                    .is_multiple_of(SERVER_CONFIG.clock.sync_interval.get())
+                {
+                    self.send_time(server_tx);
+                }
             }
             _ => {}
         }
     }
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Time {
-    ticks: u16,
+    day: u64,
+    ticks: f32,
+    speed: f32,
 }
 
 impl Time {
+    pub fn advanced(self, dt: Duration) -> Self {
+        let ticks = dt.as_secs_f32() / event_loop::tick_duration().as_secs_f32() * self.speed;
+        let ticks = self.ticks + ticks;
+        let ticks_per_day = SERVER_CONFIG.clock.ticks_per_day as f32;
+        Self {
+            day: self
+                .day
+                .saturating_add((ticks / ticks_per_day).floor().max(0.0) as u64),
+            ticks: ticks.rem_euclid(ticks_per_day),
+            ..self
+        }
+    }
+
+    pub fn day(self) -> u64 {
+        self.day
+    }
+
     pub fn sky_rotation(self) -> UnitQuaternion<f32> {
-        let time = SERVER_CONFIG.clock.time(self.ticks);
-        let theta = TAU * time;
+        self.orbit_rotation(1.0, 0.0)
+    }
+
+    pub fn orbit_rotation(self, period: f32, offset: f32) -> UnitQuaternion<f32> {
+        let days = (self.day as f64 + SERVER_CONFIG.clock.time(self.ticks) as f64) / period as f64;
+        let theta = TAU * (days + offset as f64).rem_euclid(1.0) as f32;
         UnitQuaternion::new(Vector3::z() * theta)
     }
 
@@ -72,7 +172,11 @@ impl Time {
 
 impl Default for Time {
     fn default() -> Self {
-        Clock::default().time()
+        Self {
+            day: 0,
+            ticks: SERVER_CONFIG.clock.starting_ticks() as f32,
+            speed: 1.0,
+        }
     }
 }
 
@@ -109,31 +213,38 @@ impl Default for Stage {
 pub struct ClockConfig {
     ticks_per_day: u16,
     twilight_duration: u16,
-    starting_stage: StartingStage,
+    starting_stage: StageName,
+    sync_interval: NonZeroU64,
+    max_speed: f32,
 }
 
 impl ClockConfig {
-    fn starting_ticks(&self) -> u16 {
-        match self.starting_stage {
-            StartingStage::Dawn => 0,
-            StartingStage::Day => self.day_start(),
-            StartingStage::Dusk => self.dusk_start(),
-            StartingStage::Night => self.night_start(),
+    fn stage_ticks(&self, stage: StageName) -> u16 {
+        match stage {
+            StageName::Dawn => 0,
+            StageName::Day => self.day_start(),
+            StageName::Dusk => self.dusk_start(),
+            StageName::Night => self.night_start(),
         }
     }
 
-    fn time(&self, ticks: u16) -> f32 {
-        (ticks as i16 - self.horizon() as i16) as f32 / self.ticks_per_day as f32
+    fn starting_ticks(&self) -> u16 {
+        self.stage_ticks(self.starting_stage)
+    }
+
+    fn time(&self, ticks: f32) -> f32 {
+        (ticks - self.horizon() as f32) / self.ticks_per_day as f32
     }
 
-    fn stage(&self, ticks: u16) -> Stage {
-        if self.dawn_range().contains(&ticks) {
+    fn stage(&self, ticks: f32) -> Stage {
+        let tick = ticks as u16;
+        if self.dawn_range().contains(&tick) {
             Stage::Dawn {
                 progress: Self::inv_lerp(self.dawn_range(), ticks),
             }
-        } else if self.day_range().contains(&ticks) {
+        } else if self.day_range().contains(&tick) {
             Stage::Day
-        } else if self.dusk_range().contains(&ticks) {
+        } else if self.dusk_range().contains(&tick) {
             Stage::Dusk {
                 progress: Self::inv_lerp(self.dusk_range(), ticks),
             }
@@ -142,12 +253,12 @@ impl ClockConfig {
         }
     }
 
-    fn is_am(&self, ticks: u16) -> bool {
+    fn is_am(&self, ticks: f32) -> bool {
         !self.is_pm(ticks)
     }
 
-    fn is_pm(&self, ticks: u16) -> bool {
-        self.pm_range().contains(&ticks)
+    fn is_pm(&self, ticks: f32) -> bool {
+        self.pm_range().contains(&(ticks as u16))
     }
 
     fn dawn_range(&self) -> Range<u16> {
@@ -190,14 +301,14 @@ impl ClockConfig {
         self.noon() + self.ticks_per_day / 2
     }
 
-    fn inv_lerp(Range { start, end }: Range<u16>, value: u16) -> f32 {
-        (value - start) as f32 / (end - 1 - start) as f32
+    fn inv_lerp(Range { start, end }: Range<u16>, value: f32) -> f32 {
+        ((value - start as f32) / (end - 1 - start) as f32).min(1.0)
     }
 }
 
 #[derive(Clone, Copy, Deserialize)]
 #[serde(rename_all = "snake_case")]
-enum StartingStage {
+pub enum StageName {
     Dawn,
     Day,
     Dusk,
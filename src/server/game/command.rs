@@ -0,0 +1,835 @@
+use super::{
+    GameEvent, WorldHandle,
+    clock::{Clock, StageName},
+    inventory::Stack,
+    player::{GameMode, Player},
+    weather::WeatherKind,
+    world::{
+        WorldEvent,
+        block::{Block, data::STR_TO_BLOCK},
+        edit::{Region, RegionEdit},
+        entity::EntityKind,
+        explosion::Explosion,
+        mob::Behaviour,
+    },
+};
+use crate::server::{SERVER_CONFIG, ServerEvent, ServerSender};
+use crossbeam_channel::Sender;
+use nalgebra::Point3;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    iter::Peekable,
+    str::SplitWhitespace,
+};
+
+pub struct CommandRegistry(BTreeMap<&'static str, Command>);
+
+impl CommandRegistry {
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        description: &'static str,
+        handler: Handler,
+    ) {
+        self.0.insert(
+            name,
+            Command {
+                usage,
+                description,
+                handler,
+            },
+        );
+    }
+
+    pub fn execute(
+        &self,
+        input: &str,
+        player: &mut Player,
+        world: &mut WorldHandle,
+        game_tx: &Sender<GameEvent>,
+        server_tx: &ServerSender,
+    ) {
+        let mut cx = CommandContext {
+            commands: self,
+            player,
+            world,
+            game_tx,
+            server_tx,
+        };
+        let event = match self.dispatch(input, &mut cx) {
+            Ok(message) => ServerEvent::CommandFeedback {
+                message,
+                is_error: false,
+            },
+            Err(e) => ServerEvent::CommandFeedback {
+                message: e.to_string(),
+                is_error: true,
+            },
+        };
+        _ = server_tx.send(event);
+    }
+
+    fn dispatch(&self, input: &str, cx: &mut CommandContext) -> CommandResult {
+        let mut args = Args(
+            input
+                .strip_prefix('/')
+                .unwrap_or(input)
+                .split_whitespace()
+                .peekable(),
+        );
+        let name = args
+            .0
+            .next()
+            .ok_or(CommandError::MissingArgument("command"))?;
+        let command = self
+            .0
+            .get(name)
+            .ok_or_else(|| CommandError::UnknownCommand(name.into()))?;
+        (command.handler)(args, cx)
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        let mut commands = Self(Default::default());
+        commands.register("help", "/help [command]", "list commands", help);
+        commands.register("tp", "/tp <x> <y> <z>", "teleport the player", tp);
+        commands.register(
+            "time",
+            "/time <query|set <ticks|stage>|pause|resume|speed <scale>>",
+            "query or control the day cycle",
+            time,
+        );
+        commands.register(
+            "weather",
+            "/weather <query|clear|rain|snow|storm> [duration]",
+            "query or change the weather",
+            weather,
+        );
+        commands.register("give", "/give <block>", "put a block in the hotbar", give);
+        commands.register(
+            "gamemode",
+            "/gamemode <creative|spectator>",
+            "change whether blocks can be edited",
+            gamemode,
+        );
+        commands.register("seed", "/seed", "show the world seed", seed);
+        commands.register(
+            "world",
+            "/world [name]",
+            "list worlds or travel to one",
+            world,
+        );
+        commands.register(
+            "fill",
+            "/fill <x1> <y1> <z1> <x2> <y2> <z2> <block>",
+            "fill a region",
+            fill,
+        );
+        commands.register(
+            "replace",
+            "/replace <x1> <y1> <z1> <x2> <y2> <z2> <from> <to>",
+            "replace blocks in a region",
+            replace,
+        );
+        commands.register(
+            "hollow",
+            "/hollow <x1> <y1> <z1> <x2> <y2> <z2> <block>",
+            "build the walls of a region and clear its inside",
+            hollow,
+        );
+        commands.register(
+            "sphere",
+            "/sphere <x1> <y1> <z1> <x2> <y2> <z2> <block>",
+            "fill the sphere inscribed in a region",
+            sphere,
+        );
+        commands.register(
+            "copy",
+            "/copy <x1> <y1> <z1> <x2> <y2> <z2>",
+            "copy a region to the clipboard",
+            copy,
+        );
+        commands.register(
+            "paste",
+            "/paste <x> <y> <z> [quarter turns]",
+            "paste the clipboard",
+            paste,
+        );
+        commands.register(
+            "explode",
+            "/explode <x> <y> <z> <power>",
+            "blow up the blocks around a point",
+            explode,
+        );
+        commands.register("sign", "/sign [text]", "write on the hovered sign", sign);
+        commands.register(
+            "summon",
+            "/summon <block> <wander|follow>",
+            "spawn a mob at the player",
+            summon,
+        );
+        commands
+    }
+}
+
+pub type Handler = fn(Args, &mut CommandContext) -> CommandResult;
+
+pub type CommandResult = Result<String, CommandError>;
+
+struct Command {
+    usage: &'static str,
+    description: &'static str,
+    handler: Handler,
+}
+
+pub struct CommandContext<'a> {
+    pub commands: &'a CommandRegistry,
+    pub player: &'a mut Player,
+    pub world: &'a mut WorldHandle,
+    pub game_tx: &'a Sender<GameEvent>,
+    pub server_tx: &'a ServerSender,
+}
+
+impl CommandContext<'_> {
+    pub fn send_world(&self, event: WorldEvent) {
+        self.world
+            .tx
+            .send((event, self.server_tx.clone()))
+            .unwrap_or_else(|_| unreachable!());
+    }
+
+    fn ensure_can_edit(&self) -> Result<(), CommandError> {
+        if self.player.mode.can_edit() {
+            Ok(())
+        } else {
+            Err(CommandError::Failed(
+                "blocks can only be edited in creative mode".into(),
+            ))
+        }
+    }
+
+    fn edit(&self, region: Region, edit: RegionEdit) -> Result<(), CommandError> {
+        if !matches!(edit, RegionEdit::Copy) {
+            self.ensure_can_edit()?;
+        }
+        if !region.is_editable() {
+            return Err(CommandError::Failed("region is too large".into()));
+        }
+        if !matches!(edit, RegionEdit::Copy) && !region.is_within_height() {
+            let y_range = SERVER_CONFIG.world.block_y_range();
+            return Err(CommandError::Failed(format!(
+                "region must lie between y = {} and y = {}",
+                y_range.start,
+                y_range.end - 1,
+            )));
+        }
+        self.send_world(WorldEvent::RegionEdited {
+            region,
+            edit,
+            player: self.player.id,
+            area: self.player.cur,
+            ray: self.player.ray,
+        });
+        Ok(())
+    }
+}
+
+pub struct Args<'a>(Peekable<SplitWhitespace<'a>>);
+
+impl Args<'_> {
+    pub fn next<T: Argument>(&mut self, name: &'static str) -> Result<T, CommandError> {
+        let token = self.0.next().ok_or(CommandError::MissingArgument(name))?;
+        T::parse(token).ok_or_else(|| CommandError::InvalidArgument {
+            name,
+            value: token.into(),
+        })
+    }
+
+    pub fn next_or<T: Argument>(
+        &mut self,
+        name: &'static str,
+        default: T,
+    ) -> Result<T, CommandError> {
+        if self.0.peek().is_some() {
+            self.next(name)
+        } else {
+            Ok(default)
+        }
+    }
+
+    pub fn position(&mut self, origin: Point3<f32>) -> Result<Point3<f32>, CommandError> {
+        let y_range = SERVER_CONFIG.world.block_y_range();
+        let position = Point3::new(
+            self.next::<Coord>("x")?.resolve(origin.x),
+            self.next::<Coord>("y")?
+                .resolve(origin.y)
+                .clamp(y_range.start as f32, y_range.end as f32),
+            self.next::<Coord>("z")?.resolve(origin.z),
+        );
+        Ok(SERVER_CONFIG.world.border.clamp(position))
+    }
+
+    pub fn region(&mut self, origin: Point3<f32>) -> Result<Region, CommandError> {
+        let a = self.position(origin)?;
+        let b = self.position(origin)?;
+        Ok(Region::new(Self::coords(a), Self::coords(b)))
+    }
+
+    pub fn rest(self) -> String {
+        self.0.collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn finish(mut self) -> Result<(), CommandError> {
+        match self.0.next() {
+            Some(token) => Err(CommandError::UnexpectedArgument(token.into())),
+            None => Ok(()),
+        }
+    }
+
+    fn coords(position: Point3<f32>) -> Point3<i64> {
+        position.map(|c| c.floor() as i64)
+    }
+}
+
+pub trait Argument: Sized {
+    fn parse(token: &str) -> Option<Self>;
+}
+
+impl Argument for u16 {
+    fn parse(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+}
+
+impl Argument for f32 {
+    fn parse(token: &str) -> Option<Self> {
+        token.parse().ok().filter(|value: &f32| value.is_finite())
+    }
+}
+
+impl Argument for u64 {
+    fn parse(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+}
+
+impl Argument for usize {
+    fn parse(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+}
+
+impl Argument for String {
+    fn parse(token: &str) -> Option<Self> {
+        Some(token.into())
+    }
+}
+
+impl Argument for Block {
+    fn parse(token: &str) -> Option<Self> {
+        STR_TO_BLOCK.get(token).copied()
+    }
+}
+
+impl Argument for GameMode {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "creative" => Some(Self::Creative),
+            "spectator" => Some(Self::Spectator),
+            _ => None,
+        }
+    }
+}
+
+impl Argument for Behaviour {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "wander" => Some(Self::Wander),
+            "follow" => Some(Self::Follow),
+            _ => None,
+        }
+    }
+}
+
+impl Argument for StageName {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "dawn" => Some(Self::Dawn),
+            "day" => Some(Self::Day),
+            "dusk" => Some(Self::Dusk),
+            "night" => Some(Self::Night),
+            _ => None,
+        }
+    }
+}
+
+enum TimeAction {
+    Query,
+    Set,
+    Pause,
+    Resume,
+    Speed,
+}
+
+impl Argument for TimeAction {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "query" => Some(Self::Query),
+            "set" => Some(Self::Set),
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "speed" => Some(Self::Speed),
+            _ => None,
+        }
+    }
+}
+
+enum WeatherAction {
+    Query,
+    Set(WeatherKind),
+}
+
+impl Argument for WeatherAction {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "query" => Some(Self::Query),
+            "clear" => Some(Self::Set(WeatherKind::Clear)),
+            "rain" => Some(Self::Set(WeatherKind::Rain)),
+            "snow" => Some(Self::Set(WeatherKind::Snow)),
+            "storm" => Some(Self::Set(WeatherKind::Storm)),
+            _ => None,
+        }
+    }
+}
+
+struct Coord {
+    value: f32,
+    is_relative: bool,
+}
+
+impl Coord {
+    fn resolve(self, base: f32) -> f32 {
+        if self.is_relative {
+            base + self.value
+        } else {
+            self.value
+        }
+    }
+}
+
+impl Argument for Coord {
+    fn parse(token: &str) -> Option<Self> {
+        match token.strip_prefix('~') {
+            Some("") => Some(Self {
+                value: 0.0,
+                is_relative: true,
+            }),
+            Some(token) => Some(Self {
+                value: f32::parse(token)?,
+                is_relative: true,
+            }),
+            None => Some(Self {
+                value: f32::parse(token)?,
+                is_relative: false,
+            }),
+        }
+    }
+}
+
+pub enum CommandError {
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    InvalidArgument { name: &'static str, value: String },
+    UnexpectedArgument(String),
+    Failed(String),
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(name) => write!(f, "unknown command \"{name}\", try /help"),
+            Self::MissingArgument(name) => write!(f, "missing argument <{name}>"),
+            Self::InvalidArgument { name, value } => {
+                write!(f, "invalid value \"{value}\" for <{name}>")
+            }
+            Self::UnexpectedArgument(value) => write!(f, "unexpected argument \"{value}\""),
+            Self::Failed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+fn help(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let name = args.next_or("command", String::new())?;
+    args.finish()?;
+    if name.is_empty() {
+        Ok(cx
+            .commands
+            .0
+            .values()
+            .map(|command| format!("{} - {}", command.usage, command.description))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    } else {
+        let command = cx
+            .commands
+            .0
+            .get(&*name)
+            .ok_or(CommandError::UnknownCommand(name))?;
+        Ok(format!("{} - {}", command.usage, command.description))
+    }
+}
+
+fn tp(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let origin = args.position(cx.player.ray.origin)?;
+    args.finish()?;
+    _ = cx.server_tx.send(ServerEvent::PlayerTeleported { origin });
+    Ok(format!(
+        "teleported to {:.1} {:.1} {:.1}",
+        origin.x, origin.y, origin.z
+    ))
+}
+
+fn time(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    match args.next("action")? {
+        TimeAction::Query => {
+            args.finish()?;
+            Ok(format!(
+                "day: {}, time: {}, speed: {}{}",
+                cx.world.clock.day(),
+                cx.world.clock.ticks(),
+                cx.world.clock.speed(),
+                if cx.world.clock.is_paused() {
+                    ", paused"
+                } else {
+                    ""
+                }
+            ))
+        }
+        TimeAction::Set => {
+            let value = args.next::<String>("ticks|stage")?;
+            args.finish()?;
+            match (u16::parse(&value), StageName::parse(&value)) {
+                (Some(ticks), _) => cx.world.clock.set_ticks(ticks, cx.server_tx),
+                (None, Some(stage)) => cx.world.clock.set_stage(stage, cx.server_tx),
+                (None, None) => {
+                    return Err(CommandError::InvalidArgument {
+                        name: "ticks|stage",
+                        value,
+                    });
+                }
+            }
+            Ok(format!("set the time to {}", cx.world.clock.ticks()))
+        }
+        TimeAction::Pause => {
+            args.finish()?;
+            cx.world.clock.set_paused(true, cx.server_tx);
+            Ok("paused the day cycle".into())
+        }
+        TimeAction::Resume => {
+            args.finish()?;
+            cx.world.clock.set_paused(false, cx.server_tx);
+            Ok("resumed the day cycle".into())
+        }
+        TimeAction::Speed => {
+            let speed = args.next::<f32>("scale")?;
+            args.finish()?;
+            if !Clock::is_speed_allowed(speed) {
+                return Err(CommandError::InvalidArgument {
+                    name: "scale",
+                    value: speed.to_string(),
+                });
+            }
+            cx.world.clock.set_speed(speed, cx.server_tx);
+            Ok(format!("set the day cycle speed to {speed}"))
+        }
+    }
+}
+
+fn weather(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    match args.next("action")? {
+        WeatherAction::Query => {
+            args.finish()?;
+            Ok(format!(
+                "weather: {:?}, changing in {} ticks",
+                cx.world.weather.kind(),
+                cx.world.weather.remaining()
+            ))
+        }
+        WeatherAction::Set(kind) => {
+            let duration = args.next_or::<u64>("duration", 0)?;
+            args.finish()?;
+            cx.world
+                .weather
+                .set(kind, (duration != 0).then_some(duration), cx.server_tx);
+            Ok(format!("set the weather to {kind:?}"))
+        }
+    }
+}
+
+fn give(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let block = args.next::<Block>("block")?;
+    args.finish()?;
+    let stack = Stack::new(block.base(), Stack::MAX_COUNT);
+    if !cx.player.inventory.insert(stack) {
+        return Err(CommandError::Failed("no room in the inventory".into()));
+    }
+    _ = cx
+        .server_tx
+        .send(ServerEvent::InventoryUpdated(cx.player.inventory));
+    Ok(format!("gave {stack}"))
+}
+
+fn gamemode(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let mode = args.next::<GameMode>("mode")?;
+    args.finish()?;
+    cx.player.mode = mode;
+    Ok(match mode {
+        GameMode::Creative => "switched to creative mode".into(),
+        GameMode::Spectator => "switched to spectator mode".into(),
+    })
+}
+
+fn seed(args: Args, cx: &mut CommandContext) -> CommandResult {
+    args.finish()?;
+    Ok(format!("seed: {}", cx.world.profile.seed))
+}
+
+fn world(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let name = args.next_or("name", String::new())?;
+    args.finish()?;
+    if name.is_empty() {
+        Ok(SERVER_CONFIG
+            .worlds
+            .keys()
+            .map(|name| {
+                if name == cx.world.name {
+                    format!("{name} (current)")
+                } else {
+                    name.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    } else {
+        let (name, _) = SERVER_CONFIG.worlds.get_key_value(&name).ok_or_else(|| {
+            CommandError::InvalidArgument {
+                name: "name",
+                value: name.clone(),
+            }
+        })?;
+        _ = cx.game_tx.send(GameEvent::TravelRequested(name));
+        Ok(format!("travelling to {name}"))
+    }
+}
+
+fn sign(args: Args, cx: &mut CommandContext) -> CommandResult {
+    cx.ensure_can_edit()?;
+    let text = args.rest();
+    cx.send_world(WorldEvent::SignWritten { text });
+    Ok("writing on the hovered sign".into())
+}
+
+fn fill(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let region = args.region(cx.player.ray.origin)?;
+    let block = args.next("block")?;
+    args.finish()?;
+    cx.edit(region, RegionEdit::Fill(block))?;
+    Ok(format!("filling {} blocks", volume(region)))
+}
+
+fn replace(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let region = args.region(cx.player.ray.origin)?;
+    let from = args.next("from")?;
+    let to = args.next("to")?;
+    args.finish()?;
+    cx.edit(region, RegionEdit::Replace { from, to })?;
+    Ok(format!("replacing blocks in {} blocks", volume(region)))
+}
+
+fn hollow(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let region = args.region(cx.player.ray.origin)?;
+    let block = args.next("block")?;
+    args.finish()?;
+    cx.edit(region, RegionEdit::Hollow(block))?;
+    Ok(format!(
+        "building a hollow box of {} blocks",
+        volume(region)
+    ))
+}
+
+fn sphere(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let region = args.region(cx.player.ray.origin)?;
+    let block = args.next("block")?;
+    args.finish()?;
+    cx.edit(region, RegionEdit::Sphere(block))?;
+    Ok(format!("building a sphere in {} blocks", volume(region)))
+}
+
+fn copy(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let region = args.region(cx.player.ray.origin)?;
+    args.finish()?;
+    cx.edit(region, RegionEdit::Copy)?;
+    Ok(format!("copied {} blocks", volume(region)))
+}
+
+fn paste(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let origin = Args::coords(args.position(cx.player.ray.origin)?);
+    let turns = args.next_or("quarter turns", 0)?;
+    args.finish()?;
+    cx.edit(Region::new(origin, origin), RegionEdit::Paste { turns })?;
+    Ok("pasting the clipboard".into())
+}
+
+fn explode(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let center = args.position(cx.player.ray.origin)?;
+    let power = args.next::<f32>("power")?;
+    args.finish()?;
+    cx.ensure_can_edit()?;
+    let explosion = Explosion::new(center, power);
+    if !explosion.is_allowed() {
+        return Err(CommandError::InvalidArgument {
+            name: "power",
+            value: power.to_string(),
+        });
+    }
+    cx.send_world(WorldEvent::ExplosionTriggered {
+        explosion,
+        player: cx.player.id,
+        area: cx.player.cur,
+        ray: cx.player.ray,
+    });
+    Ok(format!(
+        "detonating an explosion at {:.1} {:.1} {:.1}",
+        center.x, center.y, center.z
+    ))
+}
+
+fn summon(mut args: Args, cx: &mut CommandContext) -> CommandResult {
+    let block = args.next("block")?;
+    let behaviour = args.next("behaviour")?;
+    args.finish()?;
+    cx.ensure_can_edit()?;
+    cx.send_world(WorldEvent::EntitySummoned {
+        kind: EntityKind::Mob { block, behaviour },
+        origin: cx.player.ray.origin,
+    });
+    Ok(format!("summoned a {} mob", block.name()))
+}
+
+fn volume(region: Region) -> i64 {
+    region.diagonal().product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    fn args(input: &str) -> Args<'_> {
+        Args(input.split_whitespace().peekable())
+    }
+
+    #[test]
+    fn coords_are_absolute_or_relative() {
+        let origin = Point3::new(10.0, 20.0, 30.0);
+        let position = args("1.5 ~ ~-2").position(origin).ok().unwrap();
+        assert_eq!(position, Point3::new(1.5, 20.0, 28.0));
+    }
+
+    #[test]
+    fn invalid_coords_are_rejected() {
+        for token in ["~x", "x", "nan", "inf", "~inf"] {
+            assert!(Coord::parse(token).is_none(), "{token}");
+        }
+    }
+
+    #[test]
+    fn position_is_clamped_to_the_world_height() {
+        let y_range = SERVER_CONFIG.world.block_y_range();
+        let position = args("0 100000 0").position(Point3::origin()).ok().unwrap();
+        assert_eq!(position.y, y_range.end as f32);
+        let position = args("0 -100000 0").position(Point3::origin()).ok().unwrap();
+        assert_eq!(position.y, y_range.start as f32);
+    }
+
+    #[test]
+    fn region_is_floored_and_ordered() {
+        let region = args("2.7 5 -1.5 0 3.2 1")
+            .region(Point3::origin())
+            .ok()
+            .unwrap();
+        assert_eq!(region.min(), Point3::new(0, 3, -2));
+        assert_eq!(region.diagonal(), Vector3::new(3, 3, 4));
+    }
+
+    #[test]
+    fn missing_argument_is_named() {
+        let mut args = args("1 2");
+        assert!(matches!(
+            args.position(Point3::origin()),
+            Err(CommandError::MissingArgument("z"))
+        ));
+    }
+
+    #[test]
+    fn invalid_argument_keeps_its_value() {
+        assert!(matches!(
+            args("stone").next::<Block>("block"),
+            Err(CommandError::InvalidArgument { name: "block", value }) if value == "stone"
+        ));
+        assert!(matches!(
+            args("65536").next::<u16>("ticks"),
+            Err(CommandError::InvalidArgument { name: "ticks", .. })
+        ));
+        assert!(matches!(
+            args("-1").next::<u64>("duration"),
+            Err(CommandError::InvalidArgument {
+                name: "duration",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn arguments_are_typed() {
+        let mut args = args("log creative dusk follow storm 7");
+        assert!(args.next::<Block>("block").ok() == Some(STR_TO_BLOCK["log"]));
+        assert!(matches!(args.next("mode"), Ok(GameMode::Creative)));
+        assert!(matches!(args.next("stage"), Ok(StageName::Dusk)));
+        assert!(matches!(args.next("behaviour"), Ok(Behaviour::Follow)));
+        assert!(matches!(
+            args.next("action"),
+            Ok(WeatherAction::Set(WeatherKind::Storm))
+        ));
+        assert!(matches!(args.next::<usize>("turns"), Ok(7)));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn optional_argument_falls_back_to_default() {
+        assert!(matches!(args("").next_or::<u64>("duration", 5), Ok(5)));
+        assert!(matches!(args("9").next_or::<u64>("duration", 5), Ok(9)));
+        assert!(matches!(
+            args("x").next_or::<u64>("duration", 5),
+            Err(CommandError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn trailing_argument_is_unexpected() {
+        let mut args = args("query extra");
+        assert!(matches!(args.next("action"), Ok(TimeAction::Query)));
+        assert!(matches!(
+            args.finish(),
+            Err(CommandError::UnexpectedArgument(value)) if value == "extra"
+        ));
+    }
+
+    #[test]
+    fn rest_joins_the_remaining_words() {
+        assert_eq!(args("  hello   sign  world ").rest(), "hello sign world");
+    }
+}
@@ -0,0 +1,295 @@
+use super::{
+    recipe::{self, CraftingGrid, Recipe},
+    world::block::{Block, data::STR_TO_BLOCK},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display},
+    ops::Deref,
+};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Inventory([Option<Stack>; Self::SLOT_COUNT]);
+
+impl Inventory {
+    pub const SLOT_COUNT: usize = 9;
+
+    pub fn craft(&mut self, grid: &CraftingGrid) -> Result<Stack, CraftError> {
+        let recipe = Recipe::find(grid).ok_or(CraftError::UnknownRecipe)?;
+
+        let mut inventory = *self;
+        for (block, count) in recipe::counts(grid) {
+            if !inventory.take(block, count) {
+                return Err(CraftError::MissingIngredient(block));
+            }
+        }
+        if inventory.add(recipe.output) != 0 {
+            return Err(CraftError::InventoryFull);
+        }
+
+        *self = inventory;
+        Ok(recipe.output)
+    }
+
+    pub fn insert(&mut self, stack: Stack) -> bool {
+        let mut inventory = *self;
+        if inventory.add(stack) != 0 {
+            return false;
+        }
+        *self = inventory;
+        true
+    }
+
+    pub fn fill(&mut self, stack: Stack) -> Option<Stack> {
+        let remaining = self.add(stack);
+        (remaining != 0).then(|| Stack::new(stack.block, remaining))
+    }
+
+    pub fn take(&mut self, block: Block, count: u8) -> bool {
+        let available = self
+            .0
+            .iter()
+            .flatten()
+            .filter(|stack| stack.block == block)
+            .map(|stack| stack.count as u32)
+            .sum::<u32>();
+        if available < count as u32 {
+            return false;
+        }
+
+        let mut remaining = count;
+        for slot in &mut self.0 {
+            if let Some(stack) = slot
+                && stack.block == block
+            {
+                let taken = remaining.min(stack.count);
+                stack.count -= taken;
+                remaining -= taken;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        true
+    }
+
+    fn add(&mut self, stack: Stack) -> u8 {
+        let mut remaining = stack.count;
+        for slot in self.0.iter_mut().flatten() {
+            if slot.block == stack.block {
+                let added = remaining.min(Stack::MAX_COUNT - slot.count);
+                slot.count += added;
+                remaining -= added;
+            }
+        }
+        for slot in &mut self.0 {
+            if remaining != 0 && slot.is_none() {
+                *slot = Some(Stack::new(stack.block, remaining));
+                remaining = 0;
+            }
+        }
+        remaining
+    }
+}
+
+impl Deref for Inventory {
+    type Target = [Option<Stack>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromIterator<Stack> for Inventory {
+    fn from_iter<I: IntoIterator<Item = Stack>>(stacks: I) -> Self {
+        let mut inventory = Self::default();
+        for (slot, stack) in inventory.0.iter_mut().zip(stacks) {
+            *slot = Some(stack);
+        }
+        inventory
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stack {
+    pub block: Block,
+    pub count: u8,
+}
+
+impl Stack {
+    pub const MAX_COUNT: u8 = 64;
+
+    pub fn new(block: Block, count: u8) -> Self {
+        Self { block, count }
+    }
+}
+
+impl Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.block.name())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RawStack<'a> {
+    pub block: &'a str,
+    #[serde(default = "RawStack::default_count")]
+    pub count: u8,
+}
+
+impl RawStack<'_> {
+    pub fn block(&self) -> Option<Block> {
+        STR_TO_BLOCK.get(self.block).copied()
+    }
+
+    pub fn is_count_valid(&self) -> bool {
+        (1..=Stack::MAX_COUNT).contains(&self.count)
+    }
+
+    fn default_count() -> u8 {
+        1
+    }
+}
+
+pub enum CraftError {
+    UnknownRecipe,
+    MissingIngredient(Block),
+    InventoryFull,
+}
+
+impl Display for CraftError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownRecipe => write!(f, "no recipe matches the crafting grid"),
+            Self::MissingIngredient(block) => write!(f, "not enough {}", block.name()),
+            Self::InventoryFull => write!(f, "no room in the inventory for the result"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::game::recipe::GRID_DIM;
+
+    fn grid(rows: [&str; GRID_DIM]) -> CraftingGrid {
+        rows.map(|row| {
+            let mut cells = row.chars().map(|c| match c {
+                'w' => Some(STR_TO_BLOCK["wire"]),
+                'g' => Some(STR_TO_BLOCK["glowstone"]),
+                's' => Some(STR_TO_BLOCK["sand"]),
+                _ => None,
+            });
+            std::array::from_fn(|_| cells.next().flatten())
+        })
+    }
+
+    fn stack(name: &str, count: u8) -> Stack {
+        Stack::new(STR_TO_BLOCK[name], count)
+    }
+
+    fn count(inventory: &Inventory, name: &str) -> u32 {
+        inventory
+            .iter()
+            .flatten()
+            .filter(|stack| stack.block == STR_TO_BLOCK[name])
+            .map(|stack| stack.count as u32)
+            .sum()
+    }
+
+    fn full() -> Inventory {
+        [
+            "wire",
+            "glowstone",
+            "sand",
+            "log",
+            "lever",
+            "button",
+            "lamp",
+            "door",
+            "sign",
+        ]
+        .into_iter()
+        .map(|name| stack(name, Stack::MAX_COUNT))
+        .collect()
+    }
+
+    #[test]
+    fn craft_takes_ingredients_and_adds_output() {
+        let mut inventory = Inventory::from_iter([stack("wire", 5), stack("glowstone", 1)]);
+        let output = inventory.craft(&grid([" w ", "wgw", " w "]));
+        assert!(output.is_ok_and(|output| output == stack("lamp", 1)));
+        assert_eq!(count(&inventory, "wire"), 1);
+        assert_eq!(count(&inventory, "glowstone"), 0);
+        assert_eq!(count(&inventory, "lamp"), 1);
+    }
+
+    #[test]
+    fn craft_rolls_back_on_missing_ingredient() {
+        let mut inventory = Inventory::from_iter([stack("wire", 3), stack("glowstone", 1)]);
+        let before = inventory;
+        assert!(matches!(
+            inventory.craft(&grid([" w ", "wgw", " w "])),
+            Err(CraftError::MissingIngredient(block)) if block == STR_TO_BLOCK["wire"]
+        ));
+        assert!(inventory[..] == before[..]);
+    }
+
+    #[test]
+    fn craft_rolls_back_when_output_does_not_fit() {
+        let mut inventory = full();
+        let before = inventory;
+        assert!(matches!(
+            inventory.craft(&grid([" w ", "wgw", " w "])),
+            Err(CraftError::InventoryFull)
+        ));
+        assert!(inventory[..] == before[..]);
+    }
+
+    #[test]
+    fn craft_uses_the_slots_it_frees() {
+        let mut inventory = full();
+        inventory.take(STR_TO_BLOCK["glowstone"], Stack::MAX_COUNT - 1);
+        assert!(inventory.craft(&grid(["g  ", "   ", "   "])).is_ok());
+        assert_eq!(count(&inventory, "glowstone"), 0);
+        assert_eq!(count(&inventory, "wire"), Stack::MAX_COUNT as u32 + 4);
+    }
+
+    #[test]
+    fn craft_rejects_unknown_recipe() {
+        let mut inventory = Inventory::from_iter([stack("sand", 1)]);
+        assert!(matches!(
+            inventory.craft(&grid(["s  ", "   ", "   "])),
+            Err(CraftError::UnknownRecipe)
+        ));
+        assert_eq!(count(&inventory, "sand"), 1);
+    }
+
+    #[test]
+    fn take_spans_stacks_and_frees_empty_slots() {
+        let mut inventory =
+            Inventory::from_iter([stack("log", 3), stack("sand", 1), stack("log", 4)]);
+        assert!(inventory.take(STR_TO_BLOCK["log"], 5));
+        assert!(inventory[0].is_none());
+        assert!(inventory[2] == Some(stack("log", 2)));
+    }
+
+    #[test]
+    fn take_rolls_back_when_short() {
+        let mut inventory = Inventory::from_iter([stack("log", 3), stack("log", 4)]);
+        let before = inventory;
+        assert!(!inventory.take(STR_TO_BLOCK["log"], 8));
+        assert!(inventory[..] == before[..]);
+    }
+
+    #[test]
+    fn insert_is_all_or_nothing_and_fill_is_not() {
+        let mut inventory = full();
+        inventory.take(STR_TO_BLOCK["log"], 10);
+        let before = inventory;
+        assert!(!inventory.insert(stack("log", 11)));
+        assert!(inventory[..] == before[..]);
+        assert!(inventory.fill(stack("log", 11)) == Some(stack("log", 1)));
+        assert_eq!(count(&inventory, "log"), Stack::MAX_COUNT as u32);
+    }
+}
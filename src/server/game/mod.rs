@@ -1,40 +1,151 @@
 pub mod clock;
+pub mod command;
+pub mod inventory;
 pub mod player;
+pub mod recipe;
+pub mod weather;
 pub mod world;
 
 use super::{
-    ServerSender,
+    SERVER_CONFIG, ServerEvent, ServerSender,
     event_loop::{Event, EventHandler},
 };
+use crate::client::ClientEvent;
 use clock::Clock;
-use crossbeam_channel::Sender;
+use command::CommandRegistry;
+use crossbeam_channel::{Receiver, Sender};
+use inventory::Stack;
 use player::Player;
-use std::thread;
-use world::{World, WorldEvent};
+use recipe::RECIPES;
+use std::{collections::BTreeMap, sync::LazyLock, thread};
+use weather::Weather;
+use world::{World, WorldEvent, WorldProfile, block::Block, entity::EntityKind, save::Saved};
 
 pub struct Game {
     player: Player,
-    clock: Clock,
-    world_tx: Sender<(WorldEvent, ServerSender)>,
+    worlds: BTreeMap<&'static str, WorldHandle>,
+    cur: &'static str,
+    commands: CommandRegistry,
+    game_tx: Sender<GameEvent>,
+    game_rx: Receiver<GameEvent>,
+}
+
+impl Game {
+    fn travel(&mut self, name: &'static str, server_tx: &ServerSender) {
+        if name == self.cur {
+            return;
+        }
+        let Player {
+            id: player,
+            cur: area,
+            ray,
+            ..
+        } = self.player;
+        self.worlds[self.cur]
+            .tx
+            .send((WorldEvent::PlayerLeft { player }, server_tx.clone()))
+            .unwrap_or_else(|_| unreachable!());
+        self.cur = name;
+        let world = &self.worlds[self.cur];
+        world
+            .tx
+            .send((WorldEvent::PlayerConnected { area, ray }, server_tx.clone()))
+            .unwrap_or_else(|_| unreachable!());
+        world.clock.send_time(server_tx);
+        world.weather.send_state(server_tx);
+    }
+
+    fn give(&mut self, stack: Stack, server_tx: &ServerSender) {
+        let rest = self.player.inventory.fill(stack);
+        if rest != Some(stack) {
+            _ = server_tx.send(ServerEvent::InventoryUpdated(self.player.inventory));
+        }
+        if let Some(rest) = rest {
+            let event = WorldEvent::EntitySummoned {
+                kind: EntityKind::Item(rest),
+                origin: self.player.ray.origin,
+            };
+            self.worlds[self.cur]
+                .tx
+                .send((event, server_tx.clone()))
+                .unwrap_or_else(|_| unreachable!());
+        }
+    }
+
+    fn revert(&mut self, block: Block, is_undo: bool, server_tx: &ServerSender) {
+        if !self.player.inventory.take(block, 1) {
+            _ = server_tx.send(ServerEvent::CommandFeedback {
+                message: format!(
+                    "not enough {} to {}",
+                    block.name(),
+                    if is_undo { "undo" } else { "redo" },
+                ),
+                is_error: true,
+            });
+            return;
+        }
+        _ = server_tx.send(ServerEvent::InventoryUpdated(self.player.inventory));
+
+        let Player {
+            id: player,
+            cur: area,
+            ray,
+            ..
+        } = self.player;
+        let paid = Some(block);
+        let event = if is_undo {
+            WorldEvent::EditUndone {
+                player,
+                paid,
+                area,
+                ray,
+            }
+        } else {
+            WorldEvent::EditRedone {
+                player,
+                paid,
+                area,
+                ray,
+            }
+        };
+        self.worlds[self.cur]
+            .tx
+            .send((event, server_tx.clone()))
+            .unwrap_or_else(|_| unreachable!());
+    }
 }
 
 impl Default for Game {
     fn default() -> Self {
+        LazyLock::force(&RECIPES);
         let player = Default::default();
-        let clock = Default::default();
-        let (world_tx, world_rx) = crossbeam_channel::unbounded();
-
-        thread::spawn(move || {
-            let mut world = World::default();
-            for (event, server_tx) in world_rx {
-                world.handle(&event, &server_tx);
-            }
-        });
+        let commands = Default::default();
+        let (game_tx, game_rx) = crossbeam_channel::unbounded();
+        let worlds = SERVER_CONFIG
+            .worlds
+            .iter()
+            .map(|(name, profile)| {
+                if let Some(portal) = &profile.portal {
+                    assert!(
+                        SERVER_CONFIG.worlds.contains_key(portal),
+                        "world {name} has a portal to unknown world {portal}"
+                    );
+                }
+                (&**name, WorldHandle::new(name, profile, game_tx.clone()))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let cur = worlds
+            .get_key_value(&*SERVER_CONFIG.world.spawn)
+            .map(|(name, _)| *name)
+            .unwrap_or_else(|| panic!("unknown spawn world {}", SERVER_CONFIG.world.spawn));
 
         Self {
             player,
-            clock,
-            world_tx,
+            worlds,
+            cur,
+            commands,
+            game_tx,
+            game_rx,
         }
     }
 }
@@ -43,12 +154,82 @@ impl EventHandler<Event> for Game {
     type Context<'a> = &'a ServerSender;
 
     fn handle(&mut self, event: &Event, server_tx: Self::Context<'_>) {
+        if let Event::Tick = event {
+            while let Ok(event) = self.game_rx.try_recv() {
+                match event {
+                    GameEvent::TravelRequested(name) => self.travel(name, server_tx),
+                    GameEvent::StackGiven(stack) => self.give(stack, server_tx),
+                    GameEvent::RevertRequested { block, is_undo } => {
+                        self.revert(block, is_undo, server_tx);
+                    }
+                }
+            }
+            for world in self
+                .worlds
+                .values_mut()
+                .filter(|world| world.name != self.cur)
+            {
+                world.clock.advance();
+                world.weather.advance();
+                world.clock.autosave();
+                world.weather.autosave();
+            }
+        }
         self.player.handle(event, server_tx);
-        self.clock.handle(event, server_tx);
+        let world = self
+            .worlds
+            .get_mut(self.cur)
+            .unwrap_or_else(|| unreachable!());
+        world.clock.handle(event, server_tx);
+        world.weather.handle(event, server_tx);
+        if let Event::Tick = event {
+            world.clock.autosave();
+            world.weather.autosave();
+        }
+        if let Event::Client(ClientEvent::CommandSubmitted(input)) = event {
+            self.commands
+                .execute(input, &mut self.player, world, &self.game_tx, server_tx);
+        }
         if let Some(event) = WorldEvent::new(event, &self.player) {
-            self.world_tx
+            world
+                .tx
                 .send((event, server_tx.clone()))
                 .unwrap_or_else(|_| unreachable!());
         }
     }
 }
+
+pub struct WorldHandle {
+    pub name: &'static str,
+    pub profile: &'static WorldProfile,
+    pub clock: Saved<Clock>,
+    pub weather: Saved<Weather>,
+    pub tx: Sender<(WorldEvent, ServerSender)>,
+}
+
+impl WorldHandle {
+    fn new(name: &'static str, profile: &'static WorldProfile, game_tx: Sender<GameEvent>) -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded();
+
+        thread::spawn(move || {
+            let mut world = World::new(profile, game_tx);
+            for (event, server_tx) in rx {
+                world.handle(&event, &server_tx);
+            }
+        });
+
+        Self {
+            name,
+            profile,
+            clock: Clock::load(&profile.save_dir),
+            weather: Weather::load(&profile.save_dir),
+            tx,
+        }
+    }
+}
+
+pub enum GameEvent {
+    TravelRequested(&'static str),
+    StackGiven(Stack),
+    RevertRequested { block: Block, is_undo: bool },
+}
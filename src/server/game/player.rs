@@ -1,4 +1,7 @@
-use super::world::{World, block::Block};
+use super::{
+    inventory::{Inventory, RawStack, Stack},
+    world::block::Block,
+};
 use crate::{
     client::ClientEvent,
     server::{
@@ -14,13 +17,18 @@ use serde::{
     Deserialize, Deserializer,
     de::{self, Unexpected},
 };
-use std::{ops::Deref, sync::Arc};
+use std::ops::Deref;
+use uuid::Uuid;
 
 #[derive(Default)]
 pub struct Player {
+    pub id: PlayerId,
     pub prev: WorldArea,
     pub cur: WorldArea,
     pub ray: Ray,
+    pub mode: GameMode,
+    pub inventory: Inventory,
+    pub held: Option<Block>,
 }
 
 impl EventHandler<Event> for Player {
@@ -28,6 +36,7 @@ impl EventHandler<Event> for Player {
 
     fn handle(&mut self, event: &Event, server_tx: Self::Context<'_>) {
         self.prev = self.cur;
+        self.held = None;
 
         if let Event::Client(event) = event {
             match *event {
@@ -36,29 +45,57 @@ impl EventHandler<Event> for Player {
                         origin,
                         dir,
                         speed,
-                        ref inventory,
+                        inventory,
                         ..
                     } = SERVER_CONFIG.player;
 
+                    self.id = PlayerId(Uuid::new_v4());
                     self.cur = WorldArea {
                         center: utils::chunk_coords(origin),
                         radius: render_distance as i32,
                     };
                     self.ray = Ray { origin, dir };
+                    self.inventory = inventory;
 
                     _ = server_tx.send(ServerEvent::PlayerInitialized {
                         origin,
                         dir,
                         speed,
-                        inventory: inventory.clone(),
+                        inventory,
                     });
                 }
                 ClientEvent::PlayerOrientationChanged { dir } => {
                     self.ray.dir = dir;
                 }
                 ClientEvent::PlayerPositionChanged { origin } => {
-                    self.cur.center = utils::chunk_coords(origin);
-                    self.ray.origin = origin;
+                    let clamped = SERVER_CONFIG.world.border.clamp(origin);
+                    if clamped != origin {
+                        _ = server_tx.send(ServerEvent::PlayerTeleported { origin: clamped });
+                    }
+                    self.cur.center = utils::chunk_coords(clamped);
+                    self.ray.origin = clamped;
+                }
+                ClientEvent::BlockPlaced(block) | ClientEvent::BlockUsed(Some(block)) => {
+                    if self.mode.can_edit() && self.inventory.take(block, 1) {
+                        self.held = Some(block);
+                        _ = server_tx.send(ServerEvent::InventoryUpdated(self.inventory));
+                    }
+                }
+                ClientEvent::CraftRequested(ref grid) => {
+                    let event = match self.inventory.craft(grid) {
+                        Ok(output) => {
+                            _ = server_tx.send(ServerEvent::InventoryUpdated(self.inventory));
+                            ServerEvent::CommandFeedback {
+                                message: format!("crafted {output}"),
+                                is_error: false,
+                            }
+                        }
+                        Err(e) => ServerEvent::CommandFeedback {
+                            message: e.to_string(),
+                            is_error: true,
+                        },
+                    };
+                    _ = server_tx.send(event);
                 }
                 _ => {}
             }
@@ -66,6 +103,22 @@ impl EventHandler<Event> for Player {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PlayerId(Uuid);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    #[default]
+    Creative,
+    Spectator,
+}
+
+impl GameMode {
+    pub fn can_edit(self) -> bool {
+        self == Self::Creative
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub struct WorldArea {
     center: Point3<i32>,
@@ -96,7 +149,7 @@ impl WorldArea {
             .filter(move |&coords| !other.client_contains(coords))
     }
 
-    fn server_contains(self, coords: Point3<i32>) -> bool {
+    pub fn server_contains(self, coords: Point3<i32>) -> bool {
         self.contains_xz(coords.xz())
     }
 
@@ -106,7 +159,7 @@ impl WorldArea {
 
     fn cuboid_points(self) -> impl Iterator<Item = Point3<i32>> {
         (-self.radius..=self.radius).flat_map(move |dx| {
-            World::Y_RANGE.flat_map(move |y| {
+            SERVER_CONFIG.world.y_range().flat_map(move |y| {
                 (-self.radius..=self.radius).map(move |dz| self.coords(dx, y, dz))
             })
         })
@@ -116,11 +169,15 @@ impl WorldArea {
         (-self.radius..=self.radius)
             .into_par_iter()
             .flat_map(move |dx| {
-                World::Y_RANGE.into_par_iter().flat_map(move |y| {
-                    (-self.radius..=self.radius)
-                        .into_par_iter()
-                        .map(move |dz| self.coords(dx, y, dz))
-                })
+                SERVER_CONFIG
+                    .world
+                    .y_range()
+                    .into_par_iter()
+                    .flat_map(move |y| {
+                        (-self.radius..=self.radius)
+                            .into_par_iter()
+                            .map(move |dz| self.coords(dx, y, dz))
+                    })
             })
     }
 
@@ -144,24 +201,27 @@ pub struct PlayerConfig {
     pub speed: f32,
     pub reach: f32,
     #[serde(deserialize_with = "PlayerConfig::deserialize_inventory")]
-    pub inventory: Arc<[Block]>,
+    pub inventory: Inventory,
 }
 
 impl PlayerConfig {
-    fn deserialize_inventory<'de, D>(deserializer: D) -> Result<Arc<[Block]>, D::Error>
+    fn deserialize_inventory<'de, D>(deserializer: D) -> Result<Inventory, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let inventory = Box::<[_]>::deserialize(deserializer)?;
-        if inventory.len() > 9 {
-            Err(de::Error::custom("inventory has only 9 available slots"))
+        let inventory = Vec::<RawStack>::deserialize(deserializer)?;
+        if inventory.len() > Inventory::SLOT_COUNT {
+            Err(de::Error::custom(format!(
+                "inventory has only {} available slots",
+                Inventory::SLOT_COUNT
+            )))
         } else {
             inventory
                 .into_iter()
-                .map(|str| {
-                    STR_TO_BLOCK.get(str).copied().ok_or_else(|| {
+                .map(|stack| {
+                    let block = stack.block().ok_or_else(|| {
                         de::Error::invalid_value(
-                            Unexpected::Str(str),
+                            Unexpected::Str(stack.block),
                             &&*format!(
                                 "one of [\"{}\"]",
                                 STR_TO_BLOCK
@@ -171,7 +231,15 @@ impl PlayerConfig {
                                     .join("\", \"")
                             ),
                         )
-                    })
+                    })?;
+                    if stack.is_count_valid() {
+                        Ok(Stack::new(block, stack.count))
+                    } else {
+                        Err(de::Error::invalid_value(
+                            Unexpected::Unsigned(stack.count as u64),
+                            &&*format!("a count between 1 and {}", Stack::MAX_COUNT),
+                        ))
+                    }
                 })
                 .collect()
         }
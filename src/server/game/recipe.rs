@@ -0,0 +1,297 @@
+use super::{
+    inventory::{RawStack, Stack},
+    world::block::{
+        Block,
+        data::{self, STR_TO_BLOCK},
+    },
+};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, ops::Deref, sync::LazyLock};
+
+pub const GRID_DIM: usize = 3;
+
+pub type CraftingGrid = [[Option<Block>; GRID_DIM]; GRID_DIM];
+
+pub struct Recipe {
+    ingredients: Ingredients,
+    pub output: Stack,
+}
+
+impl Recipe {
+    pub fn find(grid: &CraftingGrid) -> Option<&'static Self> {
+        RECIPES.iter().find(|recipe| recipe.matches(grid))
+    }
+
+    fn matches(&self, grid: &CraftingGrid) -> bool {
+        match &self.ingredients {
+            Ingredients::Shaped(pattern) => {
+                let Some((min, max)) = bounds(grid.iter().map(|row| &row[..])) else {
+                    return false;
+                };
+                pattern.len() == max.0 - min.0 + 1
+                    && pattern.iter().enumerate().all(|(y, row)| {
+                        row.len() == max.1 - min.1 + 1
+                            && row
+                                .iter()
+                                .enumerate()
+                                .all(|(x, &block)| grid[min.0 + y][min.1 + x] == block)
+                    })
+            }
+            Ingredients::Shapeless(ingredients) => counts(grid) == *ingredients,
+        }
+    }
+}
+
+enum Ingredients {
+    Shaped(Vec<Vec<Option<Block>>>),
+    Shapeless(FxHashMap<Block, u8>),
+}
+
+pub fn counts(grid: &CraftingGrid) -> FxHashMap<Block, u8> {
+    let mut counts = FxHashMap::default();
+    for &block in grid.iter().flatten().flatten() {
+        *counts.entry(block).or_default() += 1;
+    }
+    counts
+}
+
+fn bounds<'a, R>(rows: R) -> Option<((usize, usize), (usize, usize))>
+where
+    R: IntoIterator<Item = &'a [Option<Block>]>,
+{
+    let mut cells = rows.into_iter().enumerate().flat_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .filter(|(_, block)| block.is_some())
+            .map(move |(x, _)| (y, x))
+    });
+    let first = cells.next()?;
+    Some(cells.fold((first, first), |(min, max), (y, x)| {
+        ((min.0.min(y), min.1.min(x)), (max.0.max(y), max.1.max(x)))
+    }))
+}
+
+pub static RECIPES: LazyLock<Box<[Recipe]>> = LazyLock::new(|| {
+    let path = "assets/config/recipes.toml";
+    let contents =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+    let raw = ::toml::from_str::<RawRecipes>(&contents)
+        .unwrap_or_else(|e| panic!("failed to deserialize {path}: {e}"));
+
+    let shaped = raw.shaped.iter().map(|recipe| {
+        let output = output(&recipe.output);
+        let name = recipe.output.block;
+
+        assert!(
+            (1..=GRID_DIM).contains(&recipe.pattern.len())
+                && recipe.pattern.iter().all(|row| {
+                    (1..=GRID_DIM).contains(&row.chars().count())
+                        && row.chars().count() == recipe.pattern[0].chars().count()
+                }),
+            "pattern of recipe for \"{name}\" must have between 1 and {GRID_DIM} rows of equal length between 1 and {GRID_DIM}",
+        );
+
+        let keys = recipe
+            .key
+            .iter()
+            .map(|(&key, &block)| {
+                let mut chars = key.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    panic!("key \"{key}\" of recipe for \"{name}\" must be a single character");
+                };
+                (c, ingredient(name, block))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let pattern = recipe
+            .pattern
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        ' ' => None,
+                        c => Some(*keys.get(&c).unwrap_or_else(|| {
+                            panic!(
+                                "invalid key '{c}' in pattern of recipe for \"{name}\", expected one of [\"{}\"]",
+                                data::join(recipe.key.keys().map(Deref::deref)),
+                            )
+                        })),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            bounds(pattern.iter().map(Deref::deref)).is_some(),
+            "pattern of recipe for \"{name}\" must not be empty",
+        );
+
+        Recipe {
+            ingredients: Ingredients::Shaped(trim(pattern)),
+            output,
+        }
+    });
+
+    let shapeless = raw.shapeless.iter().map(|recipe| {
+        let output = output(&recipe.output);
+        let name = recipe.output.block;
+
+        let mut counts = FxHashMap::default();
+        for stack in &recipe.ingredients {
+            *counts
+                .entry(ingredient(name, stack.block))
+                .or_insert(0usize) += stack.count as usize;
+        }
+
+        let total = counts.values().sum::<usize>();
+        assert!(
+            (1..=GRID_DIM * GRID_DIM).contains(&total),
+            "ingredients of recipe for \"{name}\" must count between 1 and {} blocks",
+            GRID_DIM * GRID_DIM,
+        );
+
+        Recipe {
+            ingredients: Ingredients::Shapeless(
+                counts
+                    .into_iter()
+                    .map(|(block, count)| (block, count as u8))
+                    .collect(),
+            ),
+            output,
+        }
+    });
+
+    shaped.chain(shapeless).collect()
+});
+
+fn output(stack: &RawStack) -> Stack {
+    let block = stack.block().unwrap_or_else(|| {
+        panic!(
+            "invalid output \"{}\" of recipe, expected one of [\"{}\"]",
+            stack.block,
+            data::join(STR_TO_BLOCK.keys().map(Deref::deref)),
+        )
+    });
+    assert!(
+        stack.is_count_valid(),
+        "output count of recipe for \"{}\" must be between 1 and {}",
+        stack.block,
+        Stack::MAX_COUNT,
+    );
+    Stack::new(block, stack.count)
+}
+
+fn ingredient(name: &str, block: &str) -> Block {
+    STR_TO_BLOCK.get(block).copied().unwrap_or_else(|| {
+        panic!(
+            "invalid ingredient \"{block}\" of recipe for \"{name}\", expected one of [\"{}\"]",
+            data::join(STR_TO_BLOCK.keys().map(Deref::deref)),
+        )
+    })
+}
+
+fn trim(pattern: Vec<Vec<Option<Block>>>) -> Vec<Vec<Option<Block>>> {
+    let ((min_y, min_x), (max_y, max_x)) =
+        bounds(pattern.iter().map(Deref::deref)).unwrap_or_else(|| unreachable!());
+    pattern[min_y..=max_y]
+        .iter()
+        .map(|row| row[min_x..=max_x].to_vec())
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct RawRecipes<'a> {
+    #[serde(borrow, default)]
+    shaped: Vec<RawShapedRecipe<'a>>,
+    #[serde(borrow, default)]
+    shapeless: Vec<RawShapelessRecipe<'a>>,
+}
+
+#[derive(Deserialize)]
+struct RawShapedRecipe<'a> {
+    #[serde(borrow)]
+    pattern: Vec<&'a str>,
+    #[serde(borrow)]
+    key: BTreeMap<&'a str, &'a str>,
+    #[serde(borrow)]
+    output: RawStack<'a>,
+}
+
+#[derive(Deserialize)]
+struct RawShapelessRecipe<'a> {
+    #[serde(borrow)]
+    ingredients: Vec<RawStack<'a>>,
+    #[serde(borrow)]
+    output: RawStack<'a>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: [&str; GRID_DIM]) -> CraftingGrid {
+        rows.map(|row| {
+            let mut cells = row.chars().map(|c| match c {
+                'l' => Some(STR_TO_BLOCK["log"]),
+                'w' => Some(STR_TO_BLOCK["wire"]),
+                'g' => Some(STR_TO_BLOCK["glowstone"]),
+                's' => Some(STR_TO_BLOCK["sand"]),
+                _ => None,
+            });
+            std::array::from_fn(|_| cells.next().flatten())
+        })
+    }
+
+    fn output(grid: &CraftingGrid) -> Option<&'static str> {
+        Recipe::find(grid).map(|recipe| recipe.output.block.name())
+    }
+
+    #[test]
+    fn recipes_load() {
+        assert_eq!(RECIPES.len(), 9);
+    }
+
+    #[test]
+    fn pattern_is_trimmed_to_its_bounds() {
+        let log = Some(STR_TO_BLOCK["log"]);
+        let pattern = trim(vec![
+            vec![None, None, None],
+            vec![None, log, None],
+            vec![None, log, log],
+        ]);
+        assert!(pattern == [vec![log, None], vec![log, log]]);
+    }
+
+    #[test]
+    fn shaped_recipe_matches_anywhere_in_the_grid() {
+        assert_eq!(output(&grid(["w  ", "l  ", "   "])), Some("lever"));
+        assert_eq!(output(&grid(["   ", "  w", "  l"])), Some("lever"));
+        assert_eq!(output(&grid([" w ", "wgw", " w "])), Some("lamp"));
+    }
+
+    #[test]
+    fn shaped_recipe_keeps_its_shape() {
+        assert_eq!(output(&grid(["w w", " g ", "w w"])), None);
+        assert_eq!(output(&grid(["ww ", "wg ", "w  "])), None);
+        assert_eq!(output(&grid(["w  ", "l  ", "  s"])), None);
+    }
+
+    #[test]
+    fn shapeless_recipe_ignores_positions() {
+        assert_eq!(output(&grid(["   ", " g ", "   "])), Some("wire"));
+        assert_eq!(output(&grid(["l  ", "   ", "  w"])), Some("counter"));
+        assert_eq!(output(&grid(["  w", "l  ", "   "])), Some("counter"));
+    }
+
+    #[test]
+    fn shapeless_recipe_counts_ingredients() {
+        assert_eq!(output(&grid(["gg ", "   ", "   "])), None);
+        assert_eq!(output(&grid(["lw ", "w  ", "   "])), None);
+    }
+
+    #[test]
+    fn empty_grid_matches_nothing() {
+        assert_eq!(output(&grid(["   ", "   ", "   "])), None);
+    }
+}
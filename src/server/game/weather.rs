@@ -0,0 +1,193 @@
+use super::world::save::{Save, Saved};
+use crate::{
+    client::ClientEvent,
+    server::{
+        SERVER_CONFIG, ServerEvent, ServerSender,
+        event_loop::{Event, EventHandler},
+    },
+    shared::{
+        enum_map::Enum,
+        utils::{self, Lerp},
+    },
+};
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::{Hash, Hasher},
+    num::NonZeroU64,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct Weather {
+    prev: WeatherKind,
+    cur: WeatherKind,
+    transition: u64,
+    remaining: u64,
+    tick: u64,
+    seed: u64,
+}
+
+impl Weather {
+    pub fn load(save_dir: &'static str) -> Saved<Self> {
+        Saved::load(save_dir, || {
+            let mut hasher = FxHasher::default();
+            (SERVER_CONFIG.weather.seed, save_dir).hash(&mut hasher);
+            let mut weather = Self {
+                prev: WeatherKind::Clear,
+                cur: WeatherKind::Clear,
+                transition: SERVER_CONFIG.weather.transition_duration.get(),
+                remaining: 0,
+                tick: 0,
+                seed: hasher.finish(),
+            };
+            weather.remaining = weather.random_duration();
+            weather
+        })
+    }
+
+    pub fn kind(&self) -> WeatherKind {
+        self.cur
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    pub fn set(&mut self, kind: WeatherKind, duration: Option<u64>, server_tx: &ServerSender) {
+        self.change(kind, duration);
+        self.send_state(server_tx);
+    }
+
+    pub fn advance(&mut self) -> bool {
+        self.tick += 1;
+
+        let mut is_changed = false;
+        if self.remaining > 0 {
+            self.remaining -= 1;
+        } else {
+            self.change(self.random_kind(), None);
+            is_changed = true;
+        }
+
+        if self.transition < SERVER_CONFIG.weather.transition_duration.get() {
+            self.transition += 1;
+            is_changed = true;
+        }
+        is_changed
+    }
+
+    fn change(&mut self, kind: WeatherKind, duration: Option<u64>) {
+        self.prev = self.cur;
+        self.cur = kind;
+        self.transition = 0;
+        self.remaining = duration.unwrap_or_else(|| self.random_duration());
+    }
+
+    fn random_kind(&self) -> WeatherKind {
+        let kinds = WeatherKind::variants()
+            .filter(|&kind| kind != self.cur)
+            .collect::<Vec<_>>();
+        kinds[self.rng().random_range(..kinds.len())]
+    }
+
+    fn random_duration(&self) -> u64 {
+        let WeatherConfig {
+            min_duration,
+            max_duration,
+            ..
+        } = SERVER_CONFIG.weather;
+        self.rng().random_range(min_duration..=max_duration)
+    }
+
+    fn rng(&self) -> SmallRng {
+        let mut hasher = FxHasher::default();
+        (self.seed, self.tick).hash(&mut hasher);
+        SmallRng::seed_from_u64(hasher.finish())
+    }
+
+    pub fn send_state(&self, server_tx: &ServerSender) {
+        _ = server_tx.send(ServerEvent::WeatherUpdated(self.state()));
+    }
+
+    fn state(&self) -> WeatherState {
+        WeatherState {
+            prev: self.prev,
+            cur: self.cur,
+            progress: self.transition as f32
+                / SERVER_CONFIG.weather.transition_duration.get() as f32,
+        }
+    }
+}
+
+impl Save for Weather {
+    const NAME: &str = "weather";
+}
+
+impl EventHandler<Event> for Weather {
+    type Context<'a> = &'a ServerSender;
+
+    fn handle(&mut self, event: &Event, server_tx: Self::Context<'_>) {
+        match event {
+            Event::Client(ClientEvent::PlayerConnected { .. }) => {
+                self.send_state(server_tx);
+            }
+            Event::Tick => {
+                let is_changed = self.advance();
+                if is_changed {
+                    self.send_state(server_tx);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Enum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Rain,
+    Snow,
+    Storm,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeatherState {
+    prev: WeatherKind,
+    cur: WeatherKind,
+    progress: f32,
+}
+
+impl WeatherState {
+    pub fn blend<T: Lerp, F: Fn(WeatherKind) -> T>(self, f: F) -> T {
+        utils::lerp(f(self.prev), f(self.cur), self.progress.min(1.0))
+    }
+
+    pub fn pick(self, t: f32) -> WeatherKind {
+        if t < self.progress {
+            self.cur
+        } else {
+            self.prev
+        }
+    }
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self {
+            prev: Default::default(),
+            cur: Default::default(),
+            progress: 1.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WeatherConfig {
+    min_duration: u64,
+    max_duration: u64,
+    transition_duration: NonZeroU64,
+    seed: u64,
+}
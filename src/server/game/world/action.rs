@@ -1,12 +1,20 @@
-use super::block::Block;
+use super::{
+    block::Block,
+    save::{Save, Saved, palette},
+};
 use crate::shared::utils;
 use nalgebra::Point3;
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct ActionStore(pub FxHashMap<Point3<i32>, FxHashMap<Point3<u8>, BlockAction>>);
 
 impl ActionStore {
+    pub fn load(save_dir: &'static str) -> Saved<Self> {
+        Saved::load(save_dir, Default::default)
+    }
+
     pub fn get(&self, coords: Point3<i64>) -> Option<BlockAction> {
         self.0
             .get(&utils::chunk_coords(coords))?
@@ -33,6 +41,10 @@ impl ActionStore {
     }
 }
 
+impl Save for ActionStore {
+    const NAME: &str = "actions";
+}
+
 impl Extend<(Point3<i64>, BlockAction)> for ActionStore {
     fn extend<I: IntoIterator<Item = (Point3<i64>, BlockAction)>>(&mut self, iter: I) {
         for (coords, action) in iter {
@@ -41,8 +53,8 @@ impl Extend<(Point3<i64>, BlockAction)> for ActionStore {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BlockAction {
-    Place(Block),
+    Place(#[serde(with = "palette")] Block),
     Destroy,
 }
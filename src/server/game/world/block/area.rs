@@ -31,7 +31,7 @@ impl BlockArea {
     pub fn is_side_visible(&self, side: Option<Side>) -> bool {
         side.is_none_or(|side| {
             let neighbor = self[SIDE_DELTAS[side]];
-            neighbor != self.kernel() && neighbor.data().is_transparent()
+            neighbor.base() != self.kernel().base() && neighbor.data().is_transparent()
         })
     }
 
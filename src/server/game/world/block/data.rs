@@ -1,12 +1,18 @@
 use super::{
     Block, BlockLight,
     area::{BlockArea, BlockAreaLight},
-    model::{Model, RawModel},
+    fluid::{Fluid, FluidData},
+    interaction::{RawUseAction, UseAction},
+    model::{Model, RawModel, RawModelOverride},
+    power::{Power, PowerData},
+    random::{RandomTick, RawRandomTick},
+    reaction::Reaction,
+    state::Properties,
 };
 use crate::{
     client::game::world::BlockVertex,
     enum_map,
-    server::game::world::chunk::Chunk,
+    server::game::world::{block_entity::BlockEntityKind, chunk::Chunk},
     shared::{
         bound::Aabb,
         color::Rgb,
@@ -20,7 +26,60 @@ use serde::{
     Deserialize, Deserializer,
     de::{self, Unexpected},
 };
-use std::{array, fs, ops::Deref, sync::LazyLock};
+use std::{array, collections::BTreeMap, fs, ops::Deref, sync::LazyLock};
+
+pub(super) struct BlockStates {
+    pub properties: Properties,
+    pub states: Box<[BlockData]>,
+}
+
+impl BlockStates {
+    fn new(data: &RawBlockData<'static>) -> Self {
+        let properties = Properties::new(&data.properties);
+        let states = (0..properties.state_count())
+            .map(|state| {
+                let state = state as u8;
+                let mut model = data.model.clone();
+                let mut overrides = RawStateOverride::default();
+                for (conditions, state_override) in &data.states {
+                    if properties.matches(state, conditions) {
+                        state_override.model.apply(&mut model);
+                        overrides.merge(state_override);
+                    }
+                }
+                let facing = properties
+                    .value(state, Properties::FACING)
+                    .map(|value| Side::from_name(value).unwrap_or_else(|| unreachable!()));
+                let fluid = data.fluid.map(|data| Fluid {
+                    data,
+                    level: Self::parse_value(&properties, state, Fluid::LEVEL, &Fluid::LEVELS),
+                    is_falling: Self::parse_value(
+                        &properties,
+                        state,
+                        Fluid::FALLING,
+                        &Fluid::FALLING_VALUES,
+                    ) != 0,
+                });
+                let power = data.power.map(|data| {
+                    let (name, values) = Power::property(data);
+                    Power::new(data, Self::parse_value(&properties, state, name, values))
+                });
+                let on_use = data
+                    .on_use
+                    .map(|on_use| UseAction::new(on_use, &properties, state));
+                BlockData::new(data, &model, &overrides, facing, fluid, power, on_use)
+            })
+            .collect();
+        Self { properties, states }
+    }
+
+    fn parse_value(properties: &Properties, state: u8, name: &str, values: &[&str]) -> u8 {
+        properties
+            .value(state, name)
+            .and_then(|value| values.iter().position(|&v| v == value))
+            .unwrap_or_else(|| unreachable!()) as u8
+    }
+}
 
 pub struct BlockData {
     model: Model,
@@ -28,6 +87,16 @@ pub struct BlockData {
     pub light_filter: Rgb<bool>,
     pub requires_blending: bool,
     pub valid_surface: Option<Block>,
+    pub reactions: Box<[Reaction]>,
+    pub portal: bool,
+    pub resistance: f32,
+    pub explosion: Option<f32>,
+    pub update_delay: Option<u64>,
+    pub random_tick: Option<RandomTick>,
+    pub fluid: Option<Fluid>,
+    pub power: Option<Power>,
+    pub on_use: Option<UseAction>,
+    pub entity: Option<BlockEntityKind>,
 }
 
 impl BlockData {
@@ -48,7 +117,7 @@ impl BlockData {
                 let tex_coords = CORNER_TEX_COORDS[corner];
                 BlockVertex::new(
                     coords + corner_deltas[corner].component_mul(&dims.coords),
-                    self.model.tex_index,
+                    self.model.tex_index(side),
                     array::from_fn(|i| tex_coords[i] * tex_dims[i]).into(),
                     face,
                     corner_aos[corner],
@@ -65,8 +134,11 @@ impl BlockData {
         area_light: &BlockAreaLight,
     ) -> impl Iterator<Item = BlockVertex> {
         let is_externally_lit = self.is_externally_lit();
+        let lowering = self.lowering(area);
         Enum::variants()
-            .filter(|&side| area.is_side_visible(side))
+            .filter(move |&side| {
+                area.is_side_visible(side) || self.is_fluid_side_visible(side, area, lowering)
+            })
             .flat_map(move |side| {
                 self.vertices(
                     side,
@@ -77,10 +149,17 @@ impl BlockData {
                     area_light.corner_lights(side, area),
                 )
             })
+            .map(move |vertex| {
+                if vertex.coords().y > coords.y {
+                    vertex.with_lowering(lowering)
+                } else {
+                    vertex
+                }
+            })
     }
 
-    pub fn tex_index(&self) -> u8 {
-        self.model.tex_index
+    pub fn tex_index(&self, side: Option<Side>) -> u16 {
+        self.model.tex_index(side)
     }
 
     pub fn hitbox(&self, coords: Point3<i64>) -> Aabb {
@@ -102,6 +181,14 @@ impl BlockData {
         }))
     }
 
+    pub fn falls(&self) -> bool {
+        self.reactions.contains(&Reaction::Fall)
+    }
+
+    pub fn is_fluid(&self) -> bool {
+        self.fluid.is_some()
+    }
+
     pub fn is_glowing(&self) -> bool {
         self.luminance != Default::default()
     }
@@ -118,6 +205,32 @@ impl BlockData {
         !self.is_glowing() && self.light_filter == Default::default()
     }
 
+    fn lowering(&self, area: &BlockArea) -> u8 {
+        match self.fluid {
+            Some(fluid) if area[SIDE_DELTAS[Side::Top]].base() != area.kernel().base() => {
+                fluid.lowering()
+            }
+            _ => 0,
+        }
+    }
+
+    fn is_fluid_side_visible(&self, side: Option<Side>, area: &BlockArea, lowering: u8) -> bool {
+        let Some(side) = side else {
+            return false;
+        };
+        let neighbor = area[SIDE_DELTAS[side]];
+        match side {
+            Side::Top => lowering != 0 && neighbor.base() != area.kernel().base(),
+            Side::Bottom => false,
+            _ => {
+                neighbor.base() == area.kernel().base()
+                    && neighbor
+                        .fluid()
+                        .is_some_and(|fluid| fluid.lowering() > lowering)
+            }
+        }
+    }
+
     fn corners(
         corner_aos: EnumMap<Corner, u8>,
         corner_lights: EnumMap<Corner, BlockLight>,
@@ -134,19 +247,41 @@ impl BlockData {
     }
 }
 
-impl From<RawBlockData<'_>> for BlockData {
-    fn from(data: RawBlockData) -> Self {
+impl BlockData {
+    const DEFAULT_RESISTANCE: f32 = 1.0;
+
+    fn new(
+        data: &RawBlockData,
+        model: &RawModel,
+        overrides: &RawStateOverride,
+        facing: Option<Side>,
+        fluid: Option<Fluid>,
+        power: Option<Power>,
+        on_use: Option<UseAction>,
+    ) -> Self {
         Self {
-            model: data.model.into(),
-            luminance: data.luminance,
-            light_filter: data.light_filter,
-            requires_blending: data.requires_blending,
+            model: Model::new(model, facing),
+            luminance: overrides.luminance.unwrap_or(data.luminance),
+            light_filter: overrides.light_filter.unwrap_or(data.light_filter),
+            requires_blending: overrides
+                .requires_blending
+                .unwrap_or(data.requires_blending),
             valid_surface: data.valid_surface.map(|str| STR_TO_BLOCK[str]),
+            reactions: data.reactions.clone().into(),
+            portal: data.portal,
+            resistance: data.resistance.unwrap_or(Self::DEFAULT_RESISTANCE),
+            explosion: overrides.explosion.or(data.explosion),
+            update_delay: data.update_delay(),
+            random_tick: data.random_tick.map(RandomTick::new),
+            fluid,
+            power,
+            on_use,
+            entity: data.entity,
         }
     }
 }
 
-#[derive(Clone, Default, Deserialize)]
+#[derive(Default, Deserialize)]
 #[serde(default)]
 struct RawBlockData<'a> {
     #[serde(borrow, flatten)]
@@ -156,11 +291,43 @@ struct RawBlockData<'a> {
     light_filter: Rgb<bool>,
     requires_blending: bool,
     valid_surface: Option<&'a str>,
+    reactions: Vec<Reaction>,
+    portal: bool,
+    resistance: Option<f32>,
+    explosion: Option<f32>,
+    update_delay: Option<u64>,
+    #[serde(borrow)]
+    random_tick: Option<RawRandomTick<'a>>,
+    fluid: Option<FluidData>,
+    power: Option<PowerData>,
+    #[serde(borrow)]
+    on_use: Option<RawUseAction<'a>>,
+    entity: Option<BlockEntityKind>,
+    #[serde(borrow)]
+    properties: BTreeMap<&'a str, Vec<&'a str>>,
+    #[serde(borrow)]
+    states: BTreeMap<&'a str, RawStateOverride<'a>>,
 }
 
 impl<'a> RawBlockData<'a> {
-    fn tex_path(&self) -> &'a str {
-        self.model.tex_path
+    fn update_delay(&self) -> Option<u64> {
+        self.update_delay
+            .or_else(|| self.fluid.map(|fluid| fluid.tick_delay.max(1)))
+            .or_else(|| self.power.and_then(PowerData::update_delay))
+            .or_else(|| {
+                self.reactions
+                    .iter()
+                    .any(|reaction| reaction.is_immediate())
+                    .then_some(0)
+            })
+    }
+
+    fn tex_paths(&self) -> impl Iterator<Item = &'a str> {
+        self.model.tex_paths().chain(
+            self.states
+                .values()
+                .flat_map(|state_override| state_override.model.tex_paths()),
+        )
     }
 
     fn deserialize_light_filter<'de, D>(deserializer: D) -> Result<Rgb<bool>, D::Error>
@@ -179,6 +346,34 @@ impl<'a> RawBlockData<'a> {
     }
 }
 
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RawStateOverride<'a> {
+    #[serde(borrow, flatten)]
+    model: RawModelOverride<'a>,
+    luminance: Option<Rgb<u8>>,
+    #[serde(deserialize_with = "RawStateOverride::deserialize_light_filter")]
+    light_filter: Option<Rgb<bool>>,
+    requires_blending: Option<bool>,
+    explosion: Option<f32>,
+}
+
+impl RawStateOverride<'_> {
+    fn merge(&mut self, other: &Self) {
+        self.luminance = other.luminance.or(self.luminance);
+        self.light_filter = other.light_filter.or(self.light_filter);
+        self.requires_blending = other.requires_blending.or(self.requires_blending);
+        self.explosion = other.explosion.or(self.explosion);
+    }
+
+    fn deserialize_light_filter<'de, D>(deserializer: D) -> Result<Option<Rgb<bool>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RawBlockData::deserialize_light_filter(deserializer).map(Some)
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy)]
 pub enum Face {
@@ -205,7 +400,7 @@ impl From<Option<Side>> for Face {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug, Enum, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Enum, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Side {
     Bottom,
@@ -217,6 +412,43 @@ pub enum Side {
 }
 
 impl Side {
+    pub const HORIZONTAL: [Self; 4] = [Self::Front, Self::Right, Self::Back, Self::Left];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bottom => "bottom",
+            Self::Front => "front",
+            Self::Right => "right",
+            Self::Back => "back",
+            Self::Left => "left",
+            Self::Top => "top",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Enum::variants().find(|side: &Self| side.name() == name)
+    }
+
+    pub fn from_delta(delta: Vector3<i64>) -> Option<Self> {
+        Enum::variants().find(|&side| SIDE_DELTAS[side].cast() == delta)
+    }
+
+    pub fn rotate(self, facing: Self) -> Self {
+        match (facing, self) {
+            (Self::Top, Self::Front) | (Self::Bottom, Self::Back) => Self::Top,
+            (Self::Top, Self::Top) | (Self::Bottom, Self::Bottom) => Self::Back,
+            (Self::Top, Self::Back) | (Self::Bottom, Self::Front) => Self::Bottom,
+            (Self::Top, Self::Bottom) | (Self::Bottom, Self::Top) => Self::Front,
+            (Self::Top | Self::Bottom, _) | (_, Self::Top | Self::Bottom) => self,
+            _ => {
+                let index = |side| Self::HORIZONTAL.iter().position(|&s| s == side);
+                let offset = index(facing).unwrap_or_else(|| unreachable!());
+                let index = index(self).unwrap_or_else(|| unreachable!());
+                Self::HORIZONTAL[(index + offset) % Self::HORIZONTAL.len()]
+            }
+        }
+    }
+
     pub fn block_points(self) -> impl Iterator<Item = (Point3<u8>, Point3<u8>)> {
         let masks = SIDE_MASKS[self];
         (0..Chunk::DIM as u8).flat_map(move |x| {
@@ -247,23 +479,23 @@ pub enum Component {
     Corner,
 }
 
-pub(super) static BLOCK_DATA: LazyLock<Box<[BlockData]>> = LazyLock::new(|| {
+pub(super) static BLOCK_DATA: LazyLock<Box<[BlockStates]>> = LazyLock::new(|| {
     let mut data = Box::new_uninit_slice(STR_TO_BLOCK.len());
-    for (str, &Block(i)) in &*STR_TO_BLOCK {
-        data[i as usize].write(RAW_BLOCK_DATA[str].clone().into());
+    for (str, &Block { id, .. }) in &*STR_TO_BLOCK {
+        data[id as usize].write(BlockStates::new(&RAW_BLOCK_DATA[str]));
     }
     unsafe { data.assume_init() }
 });
 
 pub static STR_TO_BLOCK: LazyLock<FxHashMap<&str, Block>> = LazyLock::new(|| {
-    let mut idx = Block::HARD_CODED_VALUES.len() as u8;
+    let mut idx = Block::HARD_CODED_VALUES.len() as u16;
     RAW_BLOCK_DATA
         .keys()
         .map(|&str| {
             if let Some(i) = Block::HARD_CODED_VALUES.iter().position(|&s| s == str) {
-                (str, Block(i as u8))
+                (str, Block::new(i as u16))
             } else {
-                let entry = (str, Block(idx));
+                let entry = (str, Block::new(idx));
                 idx += 1;
                 entry
             }
@@ -272,10 +504,18 @@ pub static STR_TO_BLOCK: LazyLock<FxHashMap<&str, Block>> = LazyLock::new(|| {
 });
 
 pub static TEX_PATHS: LazyLock<FxIndexSet<&str>> = LazyLock::new(|| {
-    RAW_BLOCK_DATA
+    let paths = RAW_BLOCK_DATA
         .values()
-        .map(RawBlockData::tex_path)
-        .collect()
+        .flat_map(RawBlockData::tex_paths)
+        .collect::<FxIndexSet<_>>();
+
+    assert!(
+        paths.len() <= BlockVertex::MAX_TEX_COUNT,
+        "texture count must not exceed {}",
+        BlockVertex::MAX_TEX_COUNT,
+    );
+
+    paths
 });
 
 static RAW_BLOCK_DATA: LazyLock<FxHashMap<&str, RawBlockData>> = LazyLock::new(|| {
@@ -283,7 +523,7 @@ static RAW_BLOCK_DATA: LazyLock<FxHashMap<&str, RawBlockData>> = LazyLock::new(|
     let contents =
         fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
     let leaked_contents = Box::leak(contents.into_boxed_str());
-    let data = ::toml::from_str::<FxHashMap<_, RawBlockData>>(leaked_contents)
+    let mut data = ::toml::from_str::<FxHashMap<&str, RawBlockData>>(leaked_contents)
         .unwrap_or_else(|e| panic!("failed to deserialize {path}: {e}"));
 
     assert!(
@@ -306,16 +546,118 @@ static RAW_BLOCK_DATA: LazyLock<FxHashMap<&str, RawBlockData>> = LazyLock::new(|
     {
         panic!(
             "invalid valid_surface \"{surface}\" of block \"{block}\", expected one of [\"{}\"]",
-            data.keys()
-                .map(Deref::deref)
-                .collect::<Vec<_>>()
-                .join("\", \""),
+            join(data.keys().map(Deref::deref)),
         );
     }
 
+    if let Some((block, target)) = data
+        .iter()
+        .filter_map(|(block, data)| match data.random_tick? {
+            RawRandomTick::Become(target) => Some((block, target)),
+            _ => None,
+        })
+        .find(|(_, target)| !data.contains_key(target))
+    {
+        panic!(
+            "invalid random_tick become \"{target}\" of block \"{block}\", expected one of [\"{}\"]",
+            join(data.keys().map(Deref::deref)),
+        );
+    }
+
+    for (block, data) in &mut data {
+        if let Some(fluid) = data.fluid {
+            insert_fluid_properties(block, data, fluid);
+        }
+        if let Some(power) = data.power {
+            insert_power_properties(block, data, power);
+        }
+        validate_states(block, data);
+    }
+
     data
 });
 
+fn insert_fluid_properties(block: &str, data: &mut RawBlockData, fluid: FluidData) {
+    for (name, values) in [
+        (Fluid::LEVEL, &Fluid::LEVELS[..=fluid.spread as usize]),
+        (Fluid::FALLING, &Fluid::FALLING_VALUES[..]),
+    ] {
+        if data.properties.insert(name, values.to_vec()).is_some() {
+            panic!("property \"{name}\" of fluid block \"{block}\" must not be configured");
+        }
+    }
+}
+
+fn insert_power_properties(block: &str, data: &mut RawBlockData, power: PowerData) {
+    let (name, values) = Power::property(power);
+    if data.properties.insert(name, values.to_vec()).is_some() {
+        panic!("property \"{name}\" of power block \"{block}\" must not be configured");
+    }
+}
+
+fn validate_states(block: &str, data: &RawBlockData) {
+    if let Some((name, _)) = data.properties.iter().find(|(_, values)| values.is_empty()) {
+        panic!("property \"{name}\" of block \"{block}\" must have at least one value");
+    }
+
+    if let Some(values) = data.properties.get(Properties::FACING)
+        && let Some(value) = values.iter().find(|value| Side::from_name(value).is_none())
+    {
+        panic!(
+            "invalid {} \"{value}\" of block \"{block}\", expected one of [\"{}\"]",
+            Properties::FACING,
+            join(Enum::variants().map(Side::name)),
+        );
+    }
+
+    if data.reactions.contains(&Reaction::Support) && data.valid_surface.is_none() {
+        panic!("reaction \"support\" of block \"{block}\" requires a valid_surface");
+    }
+
+    if let Some(name) = data.on_use.and_then(RawUseAction::property)
+        && !data.properties.contains_key(name)
+    {
+        panic!(
+            "invalid property \"{name}\" in on_use of block \"{block}\", expected one of [\"{}\"]",
+            join(data.properties.keys().copied()),
+        );
+    }
+
+    assert!(
+        data.properties.values().map(Vec::len).product::<usize>() <= Block::MAX_STATE_COUNT,
+        "state count of block \"{block}\" must not exceed {}",
+        Block::MAX_STATE_COUNT,
+    );
+
+    for conditions in data.states.keys() {
+        for condition in conditions.split(',') {
+            let Some((name, value)) = condition.split_once('=') else {
+                panic!(
+                    "invalid state \"{conditions}\" of block \"{block}\", expected comma-separated \"property=value\" pairs",
+                );
+            };
+
+            let Some(values) = data.properties.get(name) else {
+                panic!(
+                    "invalid property \"{name}\" in state \"{conditions}\" of block \"{block}\", expected one of [\"{}\"]",
+                    join(data.properties.keys().copied()),
+                );
+            };
+
+            if !values.contains(&value) {
+                panic!(
+                    "invalid value \"{value}\" of property \"{name}\" in state \"{conditions}\" of block \"{block}\", expected one of [\"{}\"]",
+                    join(values.iter().copied()),
+                );
+            }
+        }
+    }
+}
+
+pub fn join<'a, I: IntoIterator<Item = &'a str>>(strs: I) -> String {
+    strs.into_iter().collect::<Vec<_>>().join("\", \"")
+}
+
 static SIDE_CORNER_SIDES: LazyLock<EnumMap<Side, EnumMap<Corner, [Side; 2]>>> =
     LazyLock::new(|| {
         enum_map! {
@@ -0,0 +1,60 @@
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, Unexpected},
+};
+
+#[derive(Clone, Copy, Deserialize)]
+pub struct FluidData {
+    #[serde(deserialize_with = "FluidData::deserialize_spread")]
+    pub spread: u8,
+    pub tick_delay: u64,
+    #[serde(default)]
+    pub forms_sources: bool,
+}
+
+impl FluidData {
+    const MAX_SPREAD: u8 = 15;
+
+    fn deserialize_spread<'de, D>(deserializer: D) -> Result<u8, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let spread = u8::deserialize(deserializer)?;
+        if (1..=Self::MAX_SPREAD).contains(&spread) {
+            Ok(spread)
+        } else {
+            Err(de::Error::invalid_value(
+                Unexpected::Unsigned(spread as u64),
+                &&*format!("a spread in the range [1, {}]", Self::MAX_SPREAD),
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Fluid {
+    pub data: FluidData,
+    pub level: u8,
+    pub is_falling: bool,
+}
+
+impl Fluid {
+    pub const LEVEL: &str = "level";
+    pub const FALLING: &str = "falling";
+    pub const LEVELS: [&str; FluidData::MAX_SPREAD as usize + 1] = [
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+    ];
+    pub const FALLING_VALUES: [&str; 2] = ["false", "true"];
+
+    pub fn is_source(self) -> bool {
+        self.level == 0 && !self.is_falling
+    }
+
+    pub fn lowering(self) -> u8 {
+        if self.is_falling {
+            0
+        } else {
+            2 + self.level * 12 / self.data.spread
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::state::Properties;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RawUseAction<'a> {
+    Toggle(&'a str),
+    Open(GuiKind),
+}
+
+impl<'a> RawUseAction<'a> {
+    pub fn property(self) -> Option<&'a str> {
+        match self {
+            Self::Toggle(name) => Some(name),
+            Self::Open(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum UseAction {
+    Toggle(u8),
+    Open(GuiKind),
+}
+
+impl UseAction {
+    pub fn new(data: RawUseAction, properties: &Properties, state: u8) -> Self {
+        match data {
+            RawUseAction::Toggle(name) => Self::Toggle(
+                properties
+                    .next_value(state, name)
+                    .unwrap_or_else(|| unreachable!()),
+            ),
+            RawUseAction::Open(gui) => Self::Open(gui),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuiKind {
+    Crafting,
+}
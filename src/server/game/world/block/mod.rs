@@ -1,40 +1,153 @@
 pub mod area;
 pub mod data;
+pub mod fluid;
+pub mod interaction;
 pub mod model;
+pub mod power;
+pub mod random;
+pub mod reaction;
+pub mod state;
 
 use super::action::BlockAction;
 use crate::shared::color::Rgb;
 use bitfield::bitfield;
-use data::{BLOCK_DATA, BlockData};
+use data::{BLOCK_DATA, BlockData, STR_TO_BLOCK, Side};
+use fluid::Fluid;
+use interaction::UseAction;
+use nalgebra::Vector3;
+use power::{Power, PowerData};
 use serde::{
     Deserialize, Deserializer, Serialize,
     de::{self, Unexpected},
 };
+use state::Properties;
 use std::{array, ops::Range};
 
-#[repr(transparent)]
-#[derive(Clone, Copy, PartialEq, Default, Serialize)]
-pub struct Block(u8);
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub struct Block {
+    id: u16,
+    state: u8,
+}
 
 impl Block {
-    const MAX_COUNT: usize = u8::MAX as usize + 1;
+    const MAX_COUNT: usize = u16::MAX as usize + 1;
+    const MAX_STATE_COUNT: usize = u8::MAX as usize + 1;
     const HARD_CODED_VALUES: &[&str] = &["air", "sand"];
-    pub const AIR: Self = Self(0);
-    pub const SAND: Self = Self(1);
+    pub const AIR: Self = Self::new(0);
+    pub const SAND: Self = Self::new(1);
+
+    const fn new(id: u16) -> Self {
+        Self { id, state: 0 }
+    }
 
     pub fn data(self) -> &'static BlockData {
-        unsafe { BLOCK_DATA.get_unchecked(self.0 as usize) }
+        unsafe {
+            BLOCK_DATA
+                .get_unchecked(self.id as usize)
+                .states
+                .get_unchecked(self.state as usize)
+        }
     }
 
-    pub fn apply(&mut self, action: BlockAction) -> bool {
-        if self.is_action_valid(action) {
-            self.apply_unchecked(action);
-            true
-        } else {
-            false
+    pub fn base(self) -> Self {
+        Self::new(self.id)
+    }
+
+    pub fn name(self) -> &'static str {
+        STR_TO_BLOCK
+            .iter()
+            .find(|&(_, &block)| block == self.base())
+            .map_or("block", |(&name, _)| name)
+    }
+
+    pub fn property(self, name: &str) -> Option<&'static str> {
+        BLOCK_DATA[self.id as usize]
+            .properties
+            .value(self.state, name)
+    }
+
+    pub fn properties(self) -> impl Iterator<Item = (&'static str, &'static str)> {
+        BLOCK_DATA[self.id as usize].properties.values(self.state)
+    }
+
+    pub fn with_property(self, name: &str, value: &str) -> Option<Self> {
+        Some(Self {
+            id: self.id,
+            state: BLOCK_DATA[self.id as usize]
+                .properties
+                .with_value(self.state, name, value)?,
+        })
+    }
+
+    pub fn fluid(self) -> Option<Fluid> {
+        self.data().fluid
+    }
+
+    pub fn with_fluid_state(self, level: u8, is_falling: bool) -> Self {
+        self.with_property(Fluid::LEVEL, Fluid::LEVELS[level as usize])
+            .and_then(|block| {
+                block.with_property(Fluid::FALLING, Fluid::FALLING_VALUES[is_falling as usize])
+            })
+            .unwrap_or_else(|| unreachable!())
+    }
+
+    pub fn power(self) -> Option<Power> {
+        self.data().power
+    }
+
+    pub fn with_power_level(self, level: u8) -> Self {
+        let Some(power) = self.power() else {
+            return self;
+        };
+        let (name, values) = Power::property(power.data);
+        let index = match power.data {
+            PowerData::Conductor => level as usize,
+            PowerData::Emitter { .. } | PowerData::Consumer => (level != 0) as usize,
+        };
+        self.with_property(name, values[index])
+            .unwrap_or_else(|| unreachable!())
+    }
+
+    pub fn is_usable(self) -> bool {
+        self.data().on_use.is_some() || self.data().entity.is_some()
+    }
+
+    pub fn used(self) -> Option<Self> {
+        match self.data().on_use? {
+            UseAction::Toggle(state) => Some(Self { state, ..self }),
+            UseAction::Open(_) => None,
         }
     }
 
+    pub fn is_replaceable(self) -> bool {
+        self == Self::AIR || self.fluid().is_some()
+    }
+
+    pub fn oriented(self, normal: Vector3<i64>, dir: Vector3<f32>) -> Self {
+        let normal_side = Side::from_delta(normal);
+        let facing_side = if dir.x.abs() > dir.z.abs() {
+            if dir.x > 0.0 { Side::Left } else { Side::Right }
+        } else if dir.z > 0.0 {
+            Side::Front
+        } else {
+            Side::Back
+        };
+        normal_side
+            .into_iter()
+            .chain([facing_side])
+            .find_map(|side| self.with_property(Properties::FACING, side.name()))
+            .unwrap_or(self)
+    }
+
+    pub fn rotated(self, turns: usize) -> Self {
+        let facing = Side::HORIZONTAL[turns % Side::HORIZONTAL.len()];
+        self.property(Properties::FACING)
+            .and_then(Side::from_name)
+            .and_then(|side| self.with_property(Properties::FACING, side.rotate(facing).name()))
+            .unwrap_or(self)
+    }
+
     pub fn apply_unchecked(&mut self, action: BlockAction) {
         *self = match action {
             BlockAction::Place(block) => block,
@@ -44,9 +157,9 @@ impl Block {
 
     pub fn is_action_valid(self, action: BlockAction) -> bool {
         match (self, action) {
-            (Self::AIR, BlockAction::Place(Self::AIR) | BlockAction::Destroy) => false,
-            (Self::AIR, BlockAction::Place(_)) | (_, BlockAction::Destroy) => true,
-            (_, BlockAction::Place(_)) => false,
+            (Self::AIR, BlockAction::Destroy) => false,
+            (_, BlockAction::Destroy) => true,
+            (_, BlockAction::Place(block)) => self.is_replaceable() && block != self,
         }
     }
 }
@@ -56,15 +169,29 @@ impl<'de> Deserialize<'de> for Block {
     where
         D: Deserializer<'de>,
     {
-        let index = u8::deserialize(deserializer)?;
+        #[derive(Deserialize)]
+        struct RawBlock {
+            id: u16,
+            state: u8,
+        }
+
+        let RawBlock { id, state } = RawBlock::deserialize(deserializer)?;
         let min = 0;
         let max = BLOCK_DATA.len();
-        if (min..max).contains(&(index as usize)) {
-            Ok(Self(index))
+        if !(min..max).contains(&(id as usize)) {
+            return Err(de::Error::invalid_value(
+                Unexpected::Unsigned(id as u64),
+                &&*format!("an id in the range [{min}, {max})"),
+            ));
+        }
+
+        let max = BLOCK_DATA[id as usize].states.len();
+        if (min..max).contains(&(state as usize)) {
+            Ok(Self { id, state })
         } else {
             Err(de::Error::invalid_value(
-                Unexpected::Unsigned(index as u64),
-                &&*format!("an index in the range [{min}, {max})"),
+                Unexpected::Unsigned(state as u64),
+                &&*format!("a state in the range [{min}, {max})"),
             ))
         }
     }
@@ -99,6 +226,12 @@ impl BlockLight {
         self
     }
 
+    pub fn is_sky_exposed(self) -> bool {
+        self.skylight()
+            .into_iter()
+            .all(|c| c == Self::COMPONENT_MAX)
+    }
+
     pub fn lum(self) -> f32 {
         (Self::linearize(self.skylight()) + Self::linearize(self.torchlight()))
             .saturate()
@@ -15,10 +15,29 @@ use walkdir::{DirEntry, WalkDir};
 
 pub struct Model {
     data: &'static ModelData,
-    pub tex_index: u8,
+    tex_indices: EnumMap<Option<Side>, u16>,
 }
 
 impl Model {
+    pub fn new(model: &RawModel, facing: Option<Side>) -> Self {
+        Self {
+            data: &MODEL_DATA[model.variant],
+            tex_indices: Enum::variants()
+                .map(|side: Option<Side>| {
+                    let tex_index = model.tex_index(side);
+                    (
+                        side.map(|side| facing.map_or(side, |f| side.rotate(f))),
+                        tex_index,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn tex_index(&self, side: Option<Side>) -> u16 {
+        self.tex_indices[side]
+    }
+
     pub fn corner_deltas(&self, side: Option<Side>) -> &'static CornerDeltas {
         self.data.corner_deltas(side)
     }
@@ -27,17 +46,8 @@ impl Model {
         self.data.hitbox(coords)
     }
 
-    pub fn flat_icon(&self) -> Option<u8> {
-        self.data.has_flat_icon.then_some(self.tex_index)
-    }
-}
-
-impl From<RawModel<'_>> for Model {
-    fn from(model: RawModel) -> Self {
-        Self {
-            data: &MODEL_DATA[model.variant],
-            tex_index: model.tex_index(),
-        }
+    pub fn flat_icon(&self) -> Option<u16> {
+        self.data.has_flat_icon.then_some(self.tex_indices[None])
     }
 }
 
@@ -88,14 +98,23 @@ pub struct RawModel<'a> {
     #[serde(rename = "model", deserialize_with = "RawModel::deserialize_variant")]
     variant: &'a str,
     #[serde(rename = "texture")]
-    pub tex_path: &'a str,
+    tex_path: &'a str,
+    #[serde(rename = "textures")]
+    side_tex_paths: FxHashMap<Side, &'a str>,
 }
 
-impl RawModel<'_> {
-    fn tex_index(&self) -> u8 {
+impl<'a> RawModel<'a> {
+    pub fn tex_paths(&self) -> impl Iterator<Item = &'a str> {
+        iter::once(self.tex_path).chain(self.side_tex_paths.values().copied())
+    }
+
+    fn tex_index(&self, side: Option<Side>) -> u16 {
+        let tex_path = side
+            .and_then(|side| self.side_tex_paths.get(&side).copied())
+            .unwrap_or(self.tex_path);
         TEX_PATHS
-            .get_index_of(self.tex_path)
-            .unwrap_or_else(|| unreachable!()) as u8
+            .get_index_of(tex_path)
+            .unwrap_or_else(|| unreachable!()) as u16
     }
 
     fn deserialize_variant<'de, D>(deserializer: D) -> Result<&'de str, D::Error>
@@ -128,10 +147,51 @@ impl Default for RawModel<'_> {
         Self {
             variant: DEFAULT_VARIANT,
             tex_path: DEFAULT_TEX_PATH,
+            side_tex_paths: Default::default(),
         }
     }
 }
 
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RawModelOverride<'a> {
+    #[serde(
+        rename = "model",
+        deserialize_with = "RawModelOverride::deserialize_variant"
+    )]
+    variant: Option<&'a str>,
+    #[serde(rename = "texture")]
+    tex_path: Option<&'a str>,
+    #[serde(rename = "textures")]
+    side_tex_paths: FxHashMap<Side, &'a str>,
+}
+
+impl<'a> RawModelOverride<'a> {
+    pub fn apply(&self, model: &mut RawModel<'a>) {
+        if let Some(variant) = self.variant {
+            model.variant = variant;
+        }
+        if let Some(tex_path) = self.tex_path {
+            model.tex_path = tex_path;
+            model.side_tex_paths.clear();
+        }
+        model.side_tex_paths.extend(&self.side_tex_paths);
+    }
+
+    pub fn tex_paths(&self) -> impl Iterator<Item = &'a str> {
+        self.tex_path
+            .into_iter()
+            .chain(self.side_tex_paths.values().copied())
+    }
+
+    fn deserialize_variant<'de, D>(deserializer: D) -> Result<Option<&'de str>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RawModel::deserialize_variant(deserializer).map(Some)
+    }
+}
+
 #[derive(Default, Deserialize)]
 #[serde(default)]
 struct RawModelData {
@@ -0,0 +1,80 @@
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, Unexpected},
+};
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerData {
+    Emitter {
+        #[serde(deserialize_with = "PowerData::deserialize_strength")]
+        strength: u8,
+        #[serde(default)]
+        pulse: Option<u64>,
+    },
+    Conductor,
+    Consumer,
+}
+
+impl PowerData {
+    pub fn update_delay(self) -> Option<u64> {
+        match self {
+            Self::Emitter { pulse, .. } => pulse.map(|pulse| pulse.max(1)),
+            Self::Conductor | Self::Consumer => Some(1),
+        }
+    }
+
+    fn deserialize_strength<'de, D>(deserializer: D) -> Result<u8, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let strength = u8::deserialize(deserializer)?;
+        if (1..=Power::MAX_LEVEL).contains(&strength) {
+            Ok(strength)
+        } else {
+            Err(de::Error::invalid_value(
+                Unexpected::Unsigned(strength as u64),
+                &&*format!("a strength in the range [1, {}]", Power::MAX_LEVEL),
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Power {
+    pub data: PowerData,
+    pub level: u8,
+}
+
+impl Power {
+    pub const MAX_LEVEL: u8 = 15;
+    pub const LEVEL: &str = "power";
+    pub const POWERED: &str = "powered";
+    pub const LEVELS: [&str; Self::MAX_LEVEL as usize + 1] = [
+        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+    ];
+    pub const POWERED_VALUES: [&str; 2] = ["false", "true"];
+
+    pub fn new(data: PowerData, value: u8) -> Self {
+        let level = match data {
+            PowerData::Emitter { strength, .. } if value != 0 => strength,
+            PowerData::Consumer if value != 0 => Self::MAX_LEVEL,
+            PowerData::Emitter { .. } | PowerData::Consumer => 0,
+            PowerData::Conductor => value,
+        };
+        Self { data, level }
+    }
+
+    pub fn property(data: PowerData) -> (&'static str, &'static [&'static str]) {
+        match data {
+            PowerData::Conductor => (Self::LEVEL, &Self::LEVELS),
+            PowerData::Emitter { .. } | PowerData::Consumer => {
+                (Self::POWERED, &Self::POWERED_VALUES)
+            }
+        }
+    }
+
+    pub fn is_powered(self) -> bool {
+        self.level != 0
+    }
+}
@@ -0,0 +1,27 @@
+use super::{Block, data::STR_TO_BLOCK};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RawRandomTick<'a> {
+    Spread,
+    Decay,
+    Become(&'a str),
+}
+
+#[derive(Clone, Copy)]
+pub enum RandomTick {
+    Spread,
+    Decay,
+    Become(Block),
+}
+
+impl RandomTick {
+    pub fn new(data: RawRandomTick) -> Self {
+        match data {
+            RawRandomTick::Spread => Self::Spread,
+            RawRandomTick::Decay => Self::Decay,
+            RawRandomTick::Become(str) => Self::Become(STR_TO_BLOCK[str]),
+        }
+    }
+}
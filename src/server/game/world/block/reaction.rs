@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reaction {
+    Support,
+    Fall,
+    Power,
+    Flow,
+}
+
+impl Reaction {
+    pub fn is_immediate(self) -> bool {
+        matches!(self, Self::Support | Self::Fall)
+    }
+}
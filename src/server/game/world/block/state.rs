@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+pub struct Properties(Box<[Property]>);
+
+impl Properties {
+    pub const FACING: &str = "facing";
+
+    pub fn new(properties: &BTreeMap<&'static str, Vec<&'static str>>) -> Self {
+        Self(
+            properties
+                .iter()
+                .map(|(&name, values)| Property {
+                    name,
+                    values: values.as_slice().into(),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.0
+            .iter()
+            .map(|property| property.values.len())
+            .product()
+    }
+
+    pub fn value(&self, state: u8, name: &str) -> Option<&'static str> {
+        let index = self.position(name)?;
+        Some(self.0[index].values[self.value_index(state, index)])
+    }
+
+    pub fn values(&self, state: u8) -> impl Iterator<Item = (&'static str, &'static str)> {
+        self.0.iter().enumerate().map(move |(index, property)| {
+            (
+                property.name,
+                property.values[self.value_index(state, index)],
+            )
+        })
+    }
+
+    pub fn with_value(&self, state: u8, name: &str, value: &str) -> Option<u8> {
+        let index = self.position(name)?;
+        let value_index = self.0[index].values.iter().position(|&v| v == value)?;
+        let stride = self.stride(index);
+        let base = state as usize - self.value_index(state, index) * stride;
+        Some((base + value_index * stride) as u8)
+    }
+
+    pub fn next_value(&self, state: u8, name: &str) -> Option<u8> {
+        let index = self.position(name)?;
+        let values = &self.0[index].values;
+        let next = values[(self.value_index(state, index) + 1) % values.len()];
+        self.with_value(state, name, next)
+    }
+
+    pub fn matches(&self, state: u8, conditions: &str) -> bool {
+        conditions.split(',').all(|condition| {
+            condition
+                .split_once('=')
+                .is_some_and(|(name, value)| self.value(state, name) == Some(value))
+        })
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.0.iter().position(|property| property.name == name)
+    }
+
+    fn stride(&self, index: usize) -> usize {
+        self.0[..index]
+            .iter()
+            .map(|property| property.values.len())
+            .product()
+    }
+
+    fn value_index(&self, state: u8, index: usize) -> usize {
+        state as usize / self.stride(index) % self.0[index].values.len()
+    }
+}
+
+struct Property {
+    name: &'static str,
+    values: Box<[&'static str]>,
+}
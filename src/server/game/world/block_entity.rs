@@ -0,0 +1,159 @@
+use super::{
+    block::Block,
+    chunk::Chunk,
+    save::{Save, Saved, palette},
+};
+use crate::{server::SERVER_CONFIG, shared::utils};
+use nalgebra::Point3;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct BlockEntityStore {
+    chunks: FxHashMap<Point3<i32>, FxHashMap<Point3<u8>, BlockEntity>>,
+}
+
+impl BlockEntityStore {
+    pub fn load(save_dir: &'static str) -> Saved<Self> {
+        Saved::load(save_dir, Default::default)
+    }
+
+    pub fn get(&self, coords: Point3<i64>) -> Option<&BlockEntity> {
+        self.chunks
+            .get(&utils::chunk_coords(coords))?
+            .get(&utils::block_coords(coords))
+    }
+
+    pub fn get_mut(&mut self, coords: Point3<i64>) -> Option<&mut BlockEntity> {
+        self.chunks
+            .get_mut(&utils::chunk_coords(coords))?
+            .get_mut(&utils::block_coords(coords))
+    }
+
+    pub fn chunk(&self, coords: Point3<i32>) -> impl Iterator<Item = (Point3<i64>, &BlockEntity)> {
+        self.chunks
+            .get(&coords)
+            .into_iter()
+            .flat_map(move |entities| {
+                entities.iter().map(move |(&block_coords, entity)| {
+                    (utils::coords((coords, block_coords)), entity)
+                })
+            })
+    }
+
+    pub fn retain_matching(&mut self, coords: Point3<i32>, chunk: Option<&Chunk>) {
+        let Some(entities) = self.chunks.get_mut(&coords) else {
+            return;
+        };
+        entities.retain(|&block_coords, entity| {
+            let block = chunk.map_or(Block::AIR, |chunk| chunk[block_coords]);
+            block.data().entity == Some(entity.kind())
+        });
+        if entities.is_empty() {
+            self.chunks.remove(&coords);
+        }
+    }
+
+    pub fn replace(&mut self, coords: Point3<i64>, block: Block) -> Option<Option<BlockEntity>> {
+        let kind = block.data().entity;
+        if self.get(coords).map(BlockEntity::kind) == kind {
+            return None;
+        }
+
+        let chunk_coords = utils::chunk_coords(coords);
+        let block_coords = utils::block_coords(coords);
+        let entities = self.chunks.entry(chunk_coords).or_default();
+        let prev = match kind {
+            Some(kind) => entities.insert(block_coords, BlockEntity::new(kind)),
+            None => entities.remove(&block_coords),
+        };
+        if entities.is_empty() {
+            self.chunks.remove(&chunk_coords);
+        }
+        Some(prev)
+    }
+}
+
+impl Save for BlockEntityStore {
+    const NAME: &str = "block_entities";
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEntityKind {
+    Chest,
+    Sign,
+    Counter,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum BlockEntity {
+    Chest(#[serde(with = "palette::seq")] Vec<Block>),
+    Sign(String),
+    Counter(u64),
+}
+
+impl BlockEntity {
+    fn new(kind: BlockEntityKind) -> Self {
+        match kind {
+            BlockEntityKind::Chest => Self::Chest(vec![]),
+            BlockEntityKind::Sign => Self::Sign(String::new()),
+            BlockEntityKind::Counter => Self::Counter(0),
+        }
+    }
+
+    pub fn kind(&self) -> BlockEntityKind {
+        match self {
+            Self::Chest(_) => BlockEntityKind::Chest,
+            Self::Sign(_) => BlockEntityKind::Sign,
+            Self::Counter(_) => BlockEntityKind::Counter,
+        }
+    }
+
+    pub fn interact(&mut self, held: Option<Block>) -> Option<Block> {
+        match self {
+            Self::Chest(blocks) => match held {
+                Some(block) if blocks.len() < SERVER_CONFIG.block_entity.chest_capacity => {
+                    blocks.push(block);
+                    None
+                }
+                Some(block) => Some(block),
+                None => blocks.pop(),
+            },
+            Self::Sign(_) => held,
+            Self::Counter(count) => {
+                *count += 1;
+                held
+            }
+        }
+    }
+
+    pub fn drops(self) -> Vec<Block> {
+        match self {
+            Self::Chest(blocks) => blocks,
+            Self::Sign(_) | Self::Counter(_) => vec![],
+        }
+    }
+}
+
+impl Display for BlockEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Chest(blocks) => write!(
+                f,
+                "chest: {}/{} blocks",
+                blocks.len(),
+                SERVER_CONFIG.block_entity.chest_capacity
+            ),
+            Self::Sign(text) if text.is_empty() => write!(f, "sign: (blank)"),
+            Self::Sign(text) => write!(f, "sign: {text}"),
+            Self::Counter(count) => write!(f, "counter: {count}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BlockEntityConfig {
+    chest_capacity: usize,
+}
@@ -0,0 +1,87 @@
+use super::chunk::Chunk;
+use nalgebra::{Point2, Point3, Vector2};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WorldBorder {
+    center: Point2<i64>,
+    radius: i64,
+}
+
+impl WorldBorder {
+    pub fn min(self) -> Point2<i64> {
+        self.center - Vector2::repeat(self.radius)
+    }
+
+    pub fn max(self) -> Point2<i64> {
+        self.center + Vector2::repeat(self.radius)
+    }
+
+    pub fn contains(self, xz: Point2<i64>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (0..2).all(|axis| (min[axis]..max[axis]).contains(&xz[axis]))
+    }
+
+    pub fn contains_chunk(self, xz: Point2<i32>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let chunk_min = xz.cast::<i64>() * Chunk::DIM as i64;
+        (0..2).all(|axis| {
+            chunk_min[axis] < max[axis] && chunk_min[axis] + Chunk::DIM as i64 > min[axis]
+        })
+    }
+
+    pub fn clamp(self, mut origin: Point3<f32>) -> Point3<f32> {
+        let min = self.min().cast::<f32>();
+        let max = self.max().cast::<f32>().map(f32::next_down);
+        origin.x = origin.x.clamp(min.x, max.x);
+        origin.z = origin.z.clamp(min.y, max.y);
+        origin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BORDER: WorldBorder = WorldBorder {
+        center: Point2::new(100, -50),
+        radius: 20,
+    };
+
+    #[test]
+    fn contains_is_half_open() {
+        assert!(BORDER.contains(Point2::new(80, -70)));
+        assert!(BORDER.contains(Point2::new(119, -31)));
+        assert!(!BORDER.contains(Point2::new(120, -50)));
+        assert!(!BORDER.contains(Point2::new(100, -30)));
+        assert!(!BORDER.contains(Point2::new(79, -50)));
+    }
+
+    #[test]
+    fn chunks_overlapping_the_border_are_contained() {
+        let chunk =
+            |x: i64, z: i64| Point2::new(x, z).map(|c| c.div_euclid(Chunk::DIM as i64) as i32);
+        assert!(BORDER.contains_chunk(chunk(80, -70)));
+        assert!(BORDER.contains_chunk(chunk(119, -31)));
+        assert!(!BORDER.contains_chunk(chunk(80, -70) - Vector2::x()));
+        assert!(!BORDER.contains_chunk(chunk(119, -31) + Vector2::y()));
+    }
+
+    #[test]
+    fn clamped_positions_are_contained() {
+        for origin in [
+            Point3::new(1e6, 0.0, -1e6),
+            Point3::new(-1e6, 0.0, 1e6),
+            Point3::new(120.0, 0.0, -30.0),
+        ] {
+            let origin = BORDER.clamp(origin);
+            assert!(BORDER.contains(origin.xz().map(|c| c.floor() as i64)));
+        }
+    }
+
+    #[test]
+    fn positions_inside_are_not_clamped() {
+        let origin = Point3::new(90.5, 7.0, -60.25);
+        assert_eq!(BORDER.clamp(origin), origin);
+    }
+}
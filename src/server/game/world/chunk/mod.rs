@@ -15,7 +15,7 @@ use std::{
     ops::{BitOrAssign, Index, IndexMut},
 };
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Chunk {
     blocks: ChunkDataStore<Block>,
     non_air_count: u16,
@@ -52,18 +52,6 @@ impl Chunk {
         self.glowing_count != 0
     }
 
-    pub fn apply(&mut self, coords: Point3<u8>, action: BlockAction) -> bool {
-        let block = &mut self.blocks[coords];
-        let prev = *block;
-        if block.apply(action) {
-            let curr = *block;
-            self.adjust_counts(prev, curr);
-            true
-        } else {
-            false
-        }
-    }
-
     pub fn apply_unchecked(&mut self, coords: Point3<u8>, action: BlockAction) {
         let block = &mut self.blocks[coords];
         let prev = *block;
@@ -151,7 +139,7 @@ impl BitOrAssign<BlockLight> for ChunkLight {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ChunkDataStore<T>([[[T; Chunk::DIM]; Chunk::DIM]; Chunk::DIM]);
 
 impl<T> ChunkDataStore<T> {
@@ -0,0 +1,142 @@
+use super::block::Block;
+use crate::server::SERVER_CONFIG;
+use nalgebra::{Point3, Vector3};
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub enum RegionEdit {
+    Fill(Block),
+    Replace { from: Block, to: Block },
+    Hollow(Block),
+    Sphere(Block),
+    Copy,
+    Paste { turns: usize },
+}
+
+impl RegionEdit {
+    pub fn targets<F>(self, block_at: F, region: Region) -> Vec<(Point3<i64>, Block)>
+    where
+        F: Fn(Point3<i64>) -> Block,
+    {
+        let points = region.points();
+        match self {
+            Self::Fill(block) => points.map(|coords| (coords, block)).collect(),
+            Self::Replace { from, to } => points
+                .filter(|&coords| block_at(coords).base() == from.base())
+                .map(|coords| (coords, to))
+                .collect(),
+            Self::Hollow(block) => points
+                .map(|coords| {
+                    let block = if region.is_boundary(coords) {
+                        block
+                    } else {
+                        Block::AIR
+                    };
+                    (coords, block)
+                })
+                .collect(),
+            Self::Sphere(block) => points
+                .filter(|&coords| region.is_inscribed(coords))
+                .map(|coords| (coords, block))
+                .collect(),
+            Self::Copy | Self::Paste { .. } => vec![],
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Region {
+    min: Point3<i64>,
+    max: Point3<i64>,
+}
+
+impl Region {
+    pub fn new(a: Point3<i64>, b: Point3<i64>) -> Self {
+        Self {
+            min: a.inf(&b),
+            max: a.sup(&b),
+        }
+    }
+
+    pub fn min(self) -> Point3<i64> {
+        self.min
+    }
+
+    pub fn diagonal(self) -> Vector3<i64> {
+        self.max.coords.zip_map(&self.min.coords, |max, min| {
+            max.saturating_sub(min).saturating_add(1)
+        })
+    }
+
+    pub fn is_editable(self) -> bool {
+        self.diagonal()
+            .iter()
+            .try_fold(1u64, |volume, &c| volume.checked_mul(c as u64))
+            .is_some_and(|volume| volume <= SERVER_CONFIG.edit.max_volume)
+    }
+
+    pub fn is_within_height(self) -> bool {
+        let y_range = SERVER_CONFIG.world.block_y_range();
+        y_range.contains(&self.min.y) && y_range.contains(&self.max.y)
+    }
+
+    fn points(self) -> impl Iterator<Item = Point3<i64>> {
+        (self.min.y..=self.max.y).flat_map(move |y| {
+            (self.min.x..=self.max.x)
+                .flat_map(move |x| (self.min.z..=self.max.z).map(move |z| Point3::new(x, y, z)))
+        })
+    }
+
+    fn is_boundary(self, coords: Point3<i64>) -> bool {
+        (0..3).any(|axis| coords[axis] == self.min[axis] || coords[axis] == self.max[axis])
+    }
+
+    fn is_inscribed(self, coords: Point3<i64>) -> bool {
+        let radii = self.diagonal().cast::<f32>() * 0.5;
+        let delta = coords.cast::<f32>() - self.min.cast::<f32>() + Vector3::repeat(0.5) - radii;
+        delta.component_div(&radii).norm_squared() <= 1.0
+    }
+}
+
+pub struct Clipboard {
+    diagonal: Vector3<i64>,
+    blocks: Vec<(Vector3<i64>, Block)>,
+}
+
+impl Clipboard {
+    pub fn copy<F>(block_at: F, region: Region) -> Self
+    where
+        F: Fn(Point3<i64>) -> Block,
+    {
+        Self {
+            diagonal: region.diagonal(),
+            blocks: region
+                .points()
+                .map(|coords| (coords - region.min, block_at(coords)))
+                .collect(),
+        }
+    }
+
+    pub fn paste(&self, origin: Point3<i64>, turns: usize) -> Vec<(Point3<i64>, Block)> {
+        let turns = turns % 4;
+        let mut blocks = self
+            .blocks
+            .iter()
+            .map(|&(mut delta, block)| {
+                let mut diagonal = self.diagonal;
+                for _ in 0..turns {
+                    delta = Vector3::new(diagonal.z - 1 - delta.z, delta.y, delta.x);
+                    diagonal = Vector3::new(diagonal.z, diagonal.y, diagonal.x);
+                }
+                (origin + delta, block.rotated(turns))
+            })
+            .collect::<Vec<_>>();
+        blocks.sort_by_key(|(coords, _)| coords.y);
+        blocks
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EditConfig {
+    max_volume: u64,
+}
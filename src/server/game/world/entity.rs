@@ -0,0 +1,327 @@
+use super::{
+    ChunkStore,
+    block::Block,
+    mob::{Behaviour, Brain, MobConfig},
+    path::{PathSearch, PathState},
+};
+use crate::{
+    server::{
+        SERVER_CONFIG, ServerEvent, ServerSender,
+        game::{inventory::Stack, player::WorldArea},
+    },
+    shared::{bound::Aabb, pool::ThreadPool, utils},
+};
+use nalgebra::{Point3, Vector2, Vector3};
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+pub struct EntityStore {
+    entities: FxHashMap<EntityId, Entity>,
+    visible: FxHashSet<EntityId>,
+    despawned: Vec<EntityId>,
+    next_id: u64,
+    paths: ThreadPool<PathRequest, (EntityId, PathState)>,
+}
+
+type PathRequest = (EntityId, PathSearch, ChunkStore);
+
+impl EntityStore {
+    pub fn spawn(
+        &mut self,
+        kind: EntityKind,
+        origin: Point3<f32>,
+        velocity: Vector3<f32>,
+    ) -> EntityId {
+        let id = EntityId(self.next_id);
+        self.next_id += 1;
+        self.entities
+            .insert(id, Entity::new(id, kind, origin, velocity));
+        id
+    }
+
+    pub fn despawn(&mut self, id: EntityId) {
+        if self.entities.remove(&id).is_some() {
+            self.despawned.push(id);
+        }
+    }
+
+    pub fn tick(&mut self, chunks: &ChunkStore, area: WorldArea, target: Point3<f32>) {
+        while let Ok((id, state)) = self.paths.try_recv() {
+            if let Some(brain) = self
+                .entities
+                .get_mut(&id)
+                .and_then(|entity| entity.brain.as_mut())
+            {
+                brain.receive(state);
+            }
+        }
+
+        let searches = self
+            .entities
+            .par_iter_mut()
+            .filter(|(_, entity)| entity.is_loaded(area))
+            .filter_map(|(&id, entity)| Some((id, entity.think(target)?)))
+            .collect::<Vec<_>>();
+
+        for (id, search) in searches {
+            let chunks = chunks.snapshot(search.start(), search.goal());
+            self.paths
+                .send((id, search, chunks))
+                .unwrap_or_else(|_| unreachable!());
+        }
+
+        for entity in self.entities.values_mut() {
+            if entity.is_loaded(area) {
+                entity.simulate(chunks);
+            }
+        }
+
+        let expired = self
+            .entities
+            .iter()
+            .filter(|(_, entity)| entity.is_expired())
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>();
+
+        for id in expired {
+            self.despawn(id);
+        }
+    }
+
+    pub fn reset_visible(&mut self) {
+        self.visible.clear();
+    }
+
+    pub fn sync(&mut self, area: WorldArea, server_tx: &ServerSender) {
+        for id in self.despawned.drain(..) {
+            if self.visible.remove(&id) {
+                _ = server_tx.send(ServerEvent::EntityDespawned(id));
+            }
+        }
+
+        for (&id, entity) in &mut self.entities {
+            let is_visible = area.client_contains(utils::chunk_coords(entity.origin));
+            let was_visible = self.visible.contains(&id);
+
+            if is_visible && !was_visible {
+                self.visible.insert(id);
+                _ = server_tx.send(ServerEvent::EntitySpawned {
+                    id,
+                    kind: entity.kind,
+                    origin: entity.origin,
+                    velocity: entity.velocity,
+                });
+            } else if !is_visible && was_visible {
+                self.visible.remove(&id);
+                _ = server_tx.send(ServerEvent::EntityDespawned(id));
+            } else if is_visible && entity.is_moved {
+                _ = server_tx.send(ServerEvent::EntityMoved {
+                    id,
+                    origin: entity.origin,
+                    velocity: entity.velocity,
+                });
+            }
+
+            entity.is_moved = false;
+        }
+    }
+
+    fn search((id, search, chunks): PathRequest) -> (EntityId, PathState) {
+        (id, search.run(|coords| chunks.block(coords)))
+    }
+}
+
+impl Default for EntityStore {
+    fn default() -> Self {
+        Self {
+            entities: Default::default(),
+            visible: Default::default(),
+            despawned: Default::default(),
+            next_id: 0,
+            paths: ThreadPool::new(Self::search),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EntityId(u64);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum EntityKind {
+    Item(Stack),
+    Mob { block: Block, behaviour: Behaviour },
+}
+
+impl EntityKind {
+    pub fn block(self) -> Block {
+        match self {
+            Self::Item(Stack { block, .. }) | Self::Mob { block, .. } => block,
+        }
+    }
+
+    pub fn diagonal(self) -> Vector3<f32> {
+        match self {
+            Self::Item(_) => Vector3::repeat(SERVER_CONFIG.entity.item_size),
+            Self::Mob { .. } => SERVER_CONFIG.mob.size,
+        }
+    }
+
+    fn lifetime(self) -> Option<u64> {
+        match self {
+            Self::Item(_) => Some(SERVER_CONFIG.entity.item_lifetime),
+            Self::Mob { .. } => None,
+        }
+    }
+}
+
+struct Entity {
+    kind: EntityKind,
+    origin: Point3<f32>,
+    velocity: Vector3<f32>,
+    brain: Option<Brain>,
+    age: u64,
+    is_grounded: bool,
+    is_moved: bool,
+}
+
+impl Entity {
+    const EPSILON: f32 = 0.01;
+    const SKIN: f32 = 0.001;
+
+    fn new(id: EntityId, kind: EntityKind, origin: Point3<f32>, velocity: Vector3<f32>) -> Self {
+        Self {
+            kind,
+            origin,
+            velocity,
+            brain: match kind {
+                EntityKind::Mob { behaviour, .. } => Some(Brain::new(behaviour, id.0)),
+                EntityKind::Item(_) => None,
+            },
+            age: 0,
+            is_grounded: false,
+            is_moved: false,
+        }
+    }
+
+    fn aabb(&self) -> Aabb {
+        let diagonal = self.kind.diagonal();
+        Aabb::new(self.origin - diagonal * 0.5, diagonal)
+    }
+
+    fn feet(&self) -> Point3<i64> {
+        let height = self.kind.diagonal().y * 0.5 - Self::EPSILON;
+        (self.origin - Vector3::y() * height).map(|c| c.floor() as i64)
+    }
+
+    fn is_loaded(&self, area: WorldArea) -> bool {
+        area.server_contains(utils::chunk_coords(self.origin))
+    }
+
+    fn think(&mut self, target: Point3<f32>) -> Option<PathSearch> {
+        let feet = self.feet();
+        let diagonal = self.kind.diagonal();
+        self.brain.as_mut()?.think(feet, target, diagonal)
+    }
+
+    fn steer(&mut self) {
+        let MobConfig {
+            speed, jump_speed, ..
+        } = SERVER_CONFIG.mob;
+
+        let feet = self.feet();
+        let Some(brain) = &mut self.brain else {
+            return;
+        };
+
+        if let Some(waypoint) = brain.steer(self.origin, feet) {
+            let delta = (waypoint.cast::<f32>() - self.origin).xz() + Vector2::repeat(0.5);
+            let velocity = delta.try_normalize(0.0).unwrap_or_default() * speed;
+            self.velocity.x = velocity.x;
+            self.velocity.z = velocity.y;
+            if waypoint.y > feet.y && self.is_grounded {
+                self.velocity.y = jump_speed;
+            }
+        } else {
+            self.velocity.x = 0.0;
+            self.velocity.z = 0.0;
+        }
+    }
+
+    fn simulate(&mut self, chunks: &ChunkStore) {
+        let EntityConfig { gravity, drag, .. } = SERVER_CONFIG.entity;
+
+        self.steer();
+
+        self.age += 1;
+        self.velocity.y -= gravity;
+        self.velocity *= drag;
+
+        let prev = self.origin;
+        self.is_grounded = false;
+        let steps = self.velocity.amax().ceil().max(1.0);
+        let delta = self.velocity / steps;
+
+        for _ in 0..steps as u32 {
+            for axis in 0..3 {
+                self.step(chunks, axis, delta[axis]);
+            }
+        }
+
+        self.is_moved |= self.origin != prev;
+    }
+
+    fn step(&mut self, chunks: &ChunkStore, axis: usize, delta: f32) {
+        if delta == 0.0 || self.velocity[axis] == 0.0 {
+            return;
+        }
+
+        self.origin[axis] += delta;
+
+        let obstacles = Self::obstacles(chunks, self.aabb().pad(-Self::SKIN));
+        let bound = if delta < 0.0 {
+            obstacles.map(|hitbox| hitbox.max()[axis]).reduce(f32::max)
+        } else {
+            obstacles.map(|hitbox| hitbox.min()[axis]).reduce(f32::min)
+        };
+
+        if let Some(bound) = bound {
+            let half = self.kind.diagonal()[axis] * 0.5;
+            self.origin[axis] = if delta < 0.0 {
+                bound + half
+            } else {
+                bound - half
+            };
+            self.velocity[axis] = 0.0;
+            self.is_grounded |= axis == 1 && delta < 0.0;
+        }
+    }
+
+    fn obstacles(chunks: &ChunkStore, aabb: Aabb) -> impl Iterator<Item = Aabb> {
+        let min = aabb.min().map(|c| c.floor() as i64);
+        let max = aabb.max().map(|c| c.ceil() as i64);
+        (min.x..max.x)
+            .flat_map(move |x| {
+                (min.y..max.y).flat_map(move |y| (min.z..max.z).map(move |z| Point3::new(x, y, z)))
+            })
+            .filter_map(move |coords| {
+                let block = chunks.block(coords);
+                (!block.is_replaceable()).then(|| block.data().hitbox(coords))
+            })
+            .filter(move |hitbox| hitbox.overlaps(aabb))
+    }
+
+    fn is_expired(&self) -> bool {
+        self.kind
+            .lifetime()
+            .is_some_and(|lifetime| self.age >= lifetime)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EntityConfig {
+    gravity: f32,
+    drag: f32,
+    item_size: f32,
+    item_lifetime: u64,
+}
@@ -0,0 +1,70 @@
+use super::{action::BlockAction, block::Block};
+use crate::server::SERVER_CONFIG;
+use nalgebra::{Point3, Vector3};
+use serde::Deserialize;
+
+#[derive(Clone, Copy)]
+pub struct Explosion {
+    center: Point3<f32>,
+    power: f32,
+}
+
+impl Explosion {
+    pub fn new(center: Point3<f32>, power: f32) -> Self {
+        Self { center, power }
+    }
+
+    pub fn is_allowed(self) -> bool {
+        self.power > 0.0 && self.power <= SERVER_CONFIG.explosion.max_power
+    }
+
+    pub fn targets<F>(self, block_at: F) -> Vec<(Point3<i64>, BlockAction)>
+    where
+        F: Fn(Point3<i64>) -> Block,
+    {
+        self.points()
+            .filter_map(|coords| {
+                let block = block_at(coords);
+                if block == Block::AIR || self.strength(coords) <= block.data().resistance {
+                    return None;
+                }
+                let action = match block.used() {
+                    Some(ignited) if ignited.data().explosion.is_some() => {
+                        BlockAction::Place(ignited)
+                    }
+                    _ => BlockAction::Destroy,
+                };
+                Some((coords, action))
+            })
+            .collect()
+    }
+
+    fn points(self) -> impl Iterator<Item = Point3<i64>> {
+        let radius = self
+            .power
+            .clamp(0.0, SERVER_CONFIG.explosion.max_power)
+            .ceil() as i64;
+        let center = self.center.map(|c| c.floor() as i64);
+        (-radius..=radius).flat_map(move |dy| {
+            (-radius..=radius).flat_map(move |dx| {
+                (-radius..=radius).filter_map(move |dz| {
+                    Some(Point3::new(
+                        center.x.checked_add(dx)?,
+                        center.y.checked_add(dy)?,
+                        center.z.checked_add(dz)?,
+                    ))
+                })
+            })
+        })
+    }
+
+    fn strength(self, coords: Point3<i64>) -> f32 {
+        let distance = (coords.cast::<f32>() + Vector3::repeat(0.5) - self.center).norm();
+        self.power - distance
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExplosionConfig {
+    max_power: f32,
+}
@@ -0,0 +1,170 @@
+use super::{
+    action::BlockAction,
+    block::{
+        Block,
+        data::{SIDE_DELTAS, Side},
+    },
+};
+use crate::server::SERVER_CONFIG;
+use nalgebra::{Point3, Vector3};
+
+pub fn flow<F>(block_at: F, coords: Point3<i64>) -> Option<BlockAction>
+where
+    F: Fn(Point3<i64>) -> Block + Copy,
+{
+    if !SERVER_CONFIG.world.block_y_range().contains(&coords.y) {
+        return None;
+    }
+
+    let block = block_at(coords);
+    if !block.is_replaceable() || block.fluid().is_some_and(|fluid| fluid.is_source()) {
+        return None;
+    }
+
+    match target(block_at, coords, block) {
+        Some(target) if target != block => Some(BlockAction::Place(target)),
+        None if block != Block::AIR => Some(BlockAction::Destroy),
+        _ => None,
+    }
+}
+
+fn target<F>(block_at: F, coords: Point3<i64>, block: Block) -> Option<Block>
+where
+    F: Fn(Point3<i64>) -> Block + Copy,
+{
+    let kind = block.fluid().map(|_| block.base());
+    let is_kind = |block: Block| kind.is_none_or(|kind| kind == block.base());
+
+    let above = block_at(coords + Vector3::y());
+    if above.fluid().is_some() && is_kind(above) {
+        return Some(above.base().with_fluid_state(0, true));
+    }
+
+    let (base, level) = Side::HORIZONTAL
+        .into_iter()
+        .map(|side| coords + SIDE_DELTAS[side].cast())
+        .filter_map(|coords| {
+            let neighbor = block_at(coords);
+            let fluid = neighbor.fluid()?;
+            let level = if fluid.is_source() || fluid.is_falling {
+                0
+            } else {
+                fluid.level
+            } + 1;
+            (is_kind(neighbor) && level <= fluid.data.spread && feeds(block_at, coords, neighbor))
+                .then(|| (neighbor.base(), level))
+        })
+        .min_by_key(|&(_, level)| level)?;
+
+    let source_count = Side::HORIZONTAL
+        .into_iter()
+        .filter(|&side| block_at(coords + SIDE_DELTAS[side].cast()) == base)
+        .count();
+    let below = block_at(coords - Vector3::y());
+
+    if base.fluid().is_some_and(|fluid| fluid.data.forms_sources)
+        && source_count >= 2
+        && (below == base || !below.is_replaceable())
+    {
+        Some(base)
+    } else {
+        Some(base.with_fluid_state(level, false))
+    }
+}
+
+fn feeds<F>(block_at: F, coords: Point3<i64>, block: Block) -> bool
+where
+    F: Fn(Point3<i64>) -> Block,
+{
+    let below = block_at(coords - Vector3::y());
+    !below.is_replaceable() || below == block.base()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::game::world::block::data::STR_TO_BLOCK;
+    use rustc_hash::FxHashMap;
+
+    fn coords(x: i64, z: i64) -> Point3<i64> {
+        Point3::new(x, 1, z)
+    }
+
+    fn block_at(blocks: &FxHashMap<Point3<i64>, Block>) -> impl Fn(Point3<i64>) -> Block + Copy {
+        |coords| match blocks.get(&coords) {
+            Some(&block) => block,
+            None if coords.y <= 0 => STR_TO_BLOCK["log"],
+            None => Block::AIR,
+        }
+    }
+
+    fn world<const N: usize>(blocks: [(Point3<i64>, &str); N]) -> FxHashMap<Point3<i64>, Block> {
+        blocks
+            .into_iter()
+            .map(|(coords, name)| (coords, STR_TO_BLOCK[name]))
+            .collect()
+    }
+
+    #[test]
+    fn source_spreads_one_level_sideways() {
+        let blocks = world([(coords(0, 0), "water")]);
+        let water = STR_TO_BLOCK["water"];
+        assert!(
+            flow(block_at(&blocks), coords(1, 0))
+                == Some(BlockAction::Place(water.with_fluid_state(1, false)))
+        );
+    }
+
+    #[test]
+    fn fluid_falls_into_air_below() {
+        let blocks = world([(coords(0, 0) + Vector3::y(), "water")]);
+        let water = STR_TO_BLOCK["water"];
+        assert!(
+            flow(block_at(&blocks), coords(0, 0))
+                == Some(BlockAction::Place(water.with_fluid_state(0, true)))
+        );
+    }
+
+    #[test]
+    fn two_water_sources_form_a_source() {
+        let blocks = world([(coords(-1, 0), "water"), (coords(1, 0), "water")]);
+        assert!(
+            flow(block_at(&blocks), coords(0, 0))
+                == Some(BlockAction::Place(STR_TO_BLOCK["water"]))
+        );
+    }
+
+    #[test]
+    fn two_lava_sources_do_not_form_a_source() {
+        let blocks = world([(coords(-1, 0), "lava"), (coords(1, 0), "lava")]);
+        let lava = STR_TO_BLOCK["lava"];
+        assert!(
+            flow(block_at(&blocks), coords(0, 0))
+                == Some(BlockAction::Place(lava.with_fluid_state(1, false)))
+        );
+    }
+
+    #[test]
+    fn spread_stops_at_its_limit() {
+        let water = STR_TO_BLOCK["water"];
+        let spread = water.fluid().map_or(0, |fluid| fluid.data.spread);
+        let mut blocks = world([]);
+        blocks.insert(coords(0, 0), water.with_fluid_state(spread, false));
+        assert!(flow(block_at(&blocks), coords(1, 0)).is_none());
+    }
+
+    #[test]
+    fn unfed_flow_dries_up() {
+        let water = STR_TO_BLOCK["water"];
+        let mut blocks = world([]);
+        blocks.insert(coords(0, 0), water.with_fluid_state(1, false));
+        assert!(flow(block_at(&blocks), coords(0, 0)) == Some(BlockAction::Destroy));
+    }
+
+    #[test]
+    fn sources_and_solid_blocks_do_not_flow() {
+        let blocks = world([(coords(0, 0), "water"), (coords(1, 0), "log")]);
+        assert!(flow(block_at(&blocks), coords(0, 0)).is_none());
+        assert!(flow(block_at(&blocks), coords(1, 0)).is_none());
+    }
+}
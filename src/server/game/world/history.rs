@@ -0,0 +1,209 @@
+use super::block::Block;
+use crate::server::SERVER_CONFIG;
+use nalgebra::Point3;
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+#[derive(Default)]
+pub struct EditHistory {
+    undo: VecDeque<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl EditHistory {
+    pub fn record(&mut self, edit: Edit) {
+        if !edit.changes.is_empty() {
+            self.redo.clear();
+            self.push(edit);
+        }
+    }
+
+    pub fn undo_cost(&self) -> Option<Block> {
+        self.undo.back()?.stack?.inverse().cost()
+    }
+
+    pub fn redo_cost(&self) -> Option<Block> {
+        self.redo.last()?.stack?.cost()
+    }
+
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo.pop_back()?;
+        self.redo.push(edit.clone());
+        Some(edit.inverse())
+    }
+
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo.pop()?;
+        self.push(edit.clone());
+        Some(edit)
+    }
+
+    fn push(&mut self, edit: Edit) {
+        self.undo.push_back(edit);
+        if self.undo.len() > SERVER_CONFIG.history.capacity {
+            self.undo.pop_front();
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Edit {
+    pub changes: Vec<BlockChange>,
+    pub stack: Option<StackChange>,
+}
+
+impl Edit {
+    pub fn new(changes: Vec<BlockChange>, stack: Option<StackChange>) -> Self {
+        Self { changes, stack }
+    }
+
+    fn inverse(self) -> Self {
+        Self {
+            changes: self.changes.into_iter().map(BlockChange::inverse).collect(),
+            stack: self.stack.map(StackChange::inverse),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct BlockChange {
+    pub coords: Point3<i64>,
+    pub prev: Block,
+    pub cur: Block,
+}
+
+impl BlockChange {
+    fn inverse(self) -> Self {
+        Self {
+            coords: self.coords,
+            prev: self.cur,
+            cur: self.prev,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct StackChange {
+    pub coords: Point3<i64>,
+    pub block: Block,
+    pub is_taken: bool,
+}
+
+impl StackChange {
+    fn inverse(self) -> Self {
+        Self {
+            is_taken: !self.is_taken,
+            ..self
+        }
+    }
+
+    fn cost(self) -> Option<Block> {
+        self.is_taken.then_some(self.block)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HistoryConfig {
+    capacity: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::game::world::block::data::STR_TO_BLOCK;
+
+    fn placed(x: i64) -> Edit {
+        let coords = Point3::new(x, 0, 0);
+        let block = STR_TO_BLOCK["log"];
+        Edit::new(
+            vec![BlockChange {
+                coords,
+                prev: Block::AIR,
+                cur: block,
+            }],
+            Some(StackChange {
+                coords,
+                block,
+                is_taken: true,
+            }),
+        )
+    }
+
+    fn x(edit: &Edit) -> i64 {
+        edit.changes[0].coords.x
+    }
+
+    #[test]
+    fn empty_edits_are_not_recorded() {
+        let mut history = EditHistory::default();
+        history.record(Edit::new(vec![], None));
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn oldest_edit_is_dropped_past_capacity() {
+        let capacity = SERVER_CONFIG.history.capacity;
+        let mut history = EditHistory::default();
+        for i in 0..=capacity as i64 {
+            history.record(placed(i));
+        }
+        let undone = std::iter::from_fn(|| history.undo())
+            .map(|edit| x(&edit))
+            .collect::<Vec<_>>();
+        assert_eq!(undone.len(), capacity);
+        assert_eq!(undone.last(), Some(&1));
+    }
+
+    #[test]
+    fn redo_is_capped_by_undone_edits() {
+        let mut history = EditHistory::default();
+        history.record(placed(0));
+        history.record(placed(1));
+        assert!(history.redo().is_none());
+        history.undo();
+        assert_eq!(history.redo().as_ref().map(x), Some(1));
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let mut history = EditHistory::default();
+        history.record(placed(0));
+        history.undo();
+        history.record(placed(1));
+        assert!(history.redo().is_none());
+        assert_eq!(history.undo().as_ref().map(x), Some(1));
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn undo_inverts_and_redo_replays() {
+        let log = STR_TO_BLOCK["log"];
+        let mut history = EditHistory::default();
+        history.record(placed(0));
+
+        let undone = history.undo().unwrap();
+        assert!(undone.changes[0].prev == log && undone.changes[0].cur == Block::AIR);
+        assert!(!undone.stack.unwrap().is_taken);
+
+        let redone = history.redo().unwrap();
+        assert!(redone.changes[0].prev == Block::AIR && redone.changes[0].cur == log);
+        assert!(redone.stack.unwrap().is_taken);
+    }
+
+    #[test]
+    fn only_taking_a_stack_costs() {
+        let log = STR_TO_BLOCK["log"];
+        let mut history = EditHistory::default();
+        history.record(placed(0));
+        assert!(history.undo_cost().is_none());
+        history.undo();
+        assert!(history.redo_cost() == Some(log));
+
+        let mut history = EditHistory::default();
+        history.record(placed(0).inverse());
+        assert!(history.undo_cost() == Some(log));
+        history.undo();
+        assert!(history.redo_cost().is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use super::{
-    ChunkStore, World,
+    ChunkStore,
     action::BlockAction,
     block::{
         Block, BlockLight,
@@ -12,7 +12,7 @@ use super::{
     },
     height::HeightMap,
 };
-use crate::shared::utils;
+use crate::{server::SERVER_CONFIG, shared::utils};
 use nalgebra::Point3;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -22,6 +22,7 @@ use std::{
         VecDeque,
         hash_map::{Entry, VacantEntry},
     },
+    mem,
     ops::Range,
 };
 
@@ -121,38 +122,36 @@ impl WorldLight {
 
     pub fn apply<A>(&mut self, chunks: &ChunkStore, actions: A) -> Vec<Point3<i64>>
     where
-        A: IntoIterator<Item = (Point3<i64>, BlockAction)>,
+        A: IntoIterator<Item = (Point3<i64>, Block, BlockAction)>,
     {
         let mut branch = Branch::default();
-        for (coords, action) in actions {
+        let mut destroyed = vec![];
+        for (coords, prev, action) in actions {
             match action {
                 BlockAction::Place(block) => {
+                    if Self::is_light_replaced(prev.data(), block.data()) {
+                        destroyed.push(coords);
+                    }
+                    branch.destroy_many(chunks, self, mem::take(&mut destroyed));
                     branch.place(chunks, self, coords, block.data());
                 }
-                BlockAction::Destroy => {
-                    branch.destroy(chunks, self, coords, self.flood(coords));
-                }
+                BlockAction::Destroy => destroyed.push(coords),
             }
         }
+        branch.destroy_many(chunks, self, destroyed);
         branch.merge(self)
     }
 
+    fn is_light_replaced(prev: &BlockData, cur: &BlockData) -> bool {
+        prev.is_glowing() || prev.light_filter != cur.light_filter
+    }
+
     fn block_light(&self, coords: Point3<i64>) -> BlockLight {
         self.lights
             .get(&utils::chunk_coords(coords))
             .map_or_default(|light| light[utils::block_coords(coords)])
     }
 
-    fn flood(&self, coords: Point3<i64>) -> BlockLight {
-        Self::adjacent_points(coords)
-            .map(|(side, neighbor_coords)| {
-                self.block_light(neighbor_coords)
-                    .map(|i, c| Self::value(coords, i, side, c))
-            })
-            .reduce(BlockLight::sup)
-            .unwrap_or_else(|| unreachable!())
-    }
-
     fn adjacent_points(coords: Point3<i64>) -> impl Iterator<Item = (Side, Point3<i64>)> {
         SIDE_DELTAS
             .into_iter()
@@ -209,7 +208,7 @@ impl WorldLight {
     ) -> bool {
         BlockLight::SKYLIGHT_RANGE.contains(&index)
             && neighbor_value == BlockLight::COMPONENT_MAX
-            && coords.y >= World::Y_RANGE.start as i64 * Chunk::DIM as i64 - 1
+            && coords.y >= SERVER_CONFIG.world.block_y_range().start - 1
             && is_neighbor_on_top
     }
 
@@ -270,19 +269,28 @@ impl Branch {
         }
     }
 
-    fn destroy(
-        &mut self,
-        chunks: &ChunkStore,
-        light: &WorldLight,
-        coords: Point3<i64>,
-        value: BlockLight,
-    ) {
-        for i in BlockLight::SKYLIGHT_RANGE {
-            self.place_component(chunks, light, coords, i, value.component(i));
+    fn destroy_many(&mut self, chunks: &ChunkStore, light: &WorldLight, points: Vec<Point3<i64>>) {
+        for &coords in &points {
+            let value = self.flood(light, coords);
+            for i in BlockLight::TORCHLIGHT_RANGE {
+                self.unspread_component(chunks, light, coords, i, value.component(i));
+            }
         }
 
-        for i in BlockLight::TORCHLIGHT_RANGE {
-            self.destroy_component(chunks, light, coords, i, value.component(i));
+        let values = points
+            .into_iter()
+            .map(|coords| (coords, self.flood(light, coords)))
+            .collect::<Vec<_>>();
+
+        for i in 0..BlockLight::LEN {
+            let mut levels = NodeLevels::default();
+            for &(coords, value) in &values {
+                let node = Node::new(chunks, light, coords, value.component(i));
+                if node.set_component(self, i) {
+                    levels.push(node);
+                }
+            }
+            self.spread_levels(chunks, light, i, levels);
         }
     }
 
@@ -374,7 +382,7 @@ impl Branch {
         }
     }
 
-    fn destroy_component(
+    fn unspread_component(
         &mut self,
         chunks: &ChunkStore,
         light: &WorldLight,
@@ -385,16 +393,9 @@ impl Branch {
         let node = Node::new(chunks, light, coords, value);
         let block_light = BlockLightRefMut::new(self, &node);
         let component = block_light.component(index);
-        match component.cmp(&value) {
-            Ordering::Less => {
-                block_light.set_component(index, value);
-                self.spread_nodes(chunks, light, index, [node].into());
-            }
-            Ordering::Equal => {}
-            Ordering::Greater => {
-                block_light.set_component(index, 0);
-                self.unspread_node(chunks, light, index, node.with_value(component));
-            }
+        if component > value {
+            block_light.set_component(index, 0);
+            self.unspread_node(chunks, light, index, node.with_value(component));
         }
     }
 
@@ -447,6 +448,44 @@ impl Branch {
         }
     }
 
+    fn spread_levels<'a>(
+        &mut self,
+        chunks: &'a ChunkStore,
+        light: &'a WorldLight,
+        index: usize,
+        mut levels: NodeLevels<'a>,
+    ) {
+        while let Some(node) = levels.pop() {
+            for node in node.neighbors(chunks, light, index) {
+                if node.block().data().light_filter[index % 3] {
+                    let block_light = BlockLightRefMut::new(self, &node);
+                    if block_light.component(index) < node.value {
+                        block_light.set_component(index, node.value);
+                        levels.push(node);
+                    }
+                }
+            }
+        }
+    }
+
+    fn flood(&self, light: &WorldLight, coords: Point3<i64>) -> BlockLight {
+        WorldLight::adjacent_points(coords)
+            .map(|(side, neighbor_coords)| {
+                self.block_light(light, neighbor_coords)
+                    .map(|i, c| WorldLight::value(coords, i, side, c))
+            })
+            .reduce(BlockLight::sup)
+            .unwrap_or_else(|| unreachable!())
+    }
+
+    fn block_light(&self, light: &WorldLight, coords: Point3<i64>) -> BlockLight {
+        self.values
+            .get(&utils::chunk_coords(coords))
+            .and_then(|values| values.get(&utils::block_coords(coords)))
+            .copied()
+            .unwrap_or_else(|| light.block_light(coords))
+    }
+
     fn value(data: &BlockData, index: usize) -> u8 {
         data.luminance[index % 3] * BlockLight::TORCHLIGHT_RANGE.contains(&index) as u8
     }
@@ -477,6 +516,32 @@ impl<'a, const N: usize> From<[Node<'a>; N]> for NodeDeque<'a> {
     }
 }
 
+#[derive(Default)]
+struct NodeLevels<'a> {
+    levels: [Vec<Node<'a>>; BlockLight::COMPONENT_MAX as usize + 1],
+    top: usize,
+}
+
+impl<'a> NodeLevels<'a> {
+    fn push(&mut self, node: Node<'a>) {
+        if node.value > 1 {
+            self.top = self.top.max(node.value as usize);
+            self.levels[node.value as usize].push(node);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Node<'a>> {
+        loop {
+            if let Some(node) = self.levels[self.top].pop() {
+                return Some(node);
+            } else if self.top == 0 {
+                return None;
+            }
+            self.top -= 1;
+        }
+    }
+}
+
 #[derive(Default)]
 struct UniqueNodeDeque<'a> {
     points: FxHashSet<Point3<i64>>,
@@ -644,3 +709,126 @@ impl<'a> BlockLightRefMut<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{
+        event_loop,
+        game::world::{block::data::STR_TO_BLOCK, explosion::Explosion},
+    };
+    use nalgebra::{Vector3, point};
+    use std::{sync::Arc, time::Instant};
+
+    const RADIUS: f32 = 10.0;
+
+    fn chunk_points() -> Vec<Point3<i32>> {
+        (0..3)
+            .flat_map(|x| (0..3).flat_map(move |y| (0..3).map(move |z| point![x, y, z])))
+            .collect()
+    }
+
+    fn block_points(chunk_coords: Point3<i32>) -> impl Iterator<Item = Point3<i64>> {
+        let dim = Chunk::DIM as u8;
+        (0..dim).flat_map(move |x| {
+            (0..dim).flat_map(move |y| {
+                (0..dim).map(move |z| utils::coords((chunk_coords, point![x, y, z])))
+            })
+        })
+    }
+
+    fn center() -> Point3<i64> {
+        Point3::from(Vector3::repeat(Chunk::DIM as i64 * 3 / 2))
+    }
+
+    fn lit_cave() -> (ChunkStore, WorldLight) {
+        let [wall, glowstone] = ["log", "glowstone"].map(|name| STR_TO_BLOCK[name]);
+        let lamps = [[5, -6, 0], [-5, -6, 0], [0, -6, 5], [0, -6, -5], [0, 6, 0]]
+            .map(|delta| center() + Vector3::from(delta));
+        let points = chunk_points();
+
+        let mut chunks = ChunkStore::default();
+        for &chunk_coords in &points {
+            let mut chunk = Chunk::default();
+            for coords in block_points(chunk_coords) {
+                let block = if lamps.contains(&coords) {
+                    glowstone
+                } else if (coords - center()).cast::<f32>().norm() < RADIUS {
+                    continue;
+                } else {
+                    wall
+                };
+                chunk.apply_unchecked(utils::block_coords(coords), BlockAction::Place(block));
+            }
+            chunks.chunks.insert(chunk_coords, Arc::new(chunk));
+        }
+
+        let mut heights = HeightMap::default();
+        let mut light = WorldLight::default();
+        light.extend_placeholders(heights.load_placeholders(points.iter().copied()));
+        light.par_insert_many(&chunks, &heights, &points);
+        (chunks, light)
+    }
+
+    fn explosion() -> Explosion {
+        Explosion::new(center().cast() + Vector3::new(0.5, -5.5, 0.5), 8.0)
+    }
+
+    fn apply(chunks: &mut ChunkStore, coords: Point3<i64>, action: BlockAction) -> Block {
+        let prev = chunks.block(coords);
+        let chunk = chunks
+            .chunks
+            .get_mut(&utils::chunk_coords(coords))
+            .unwrap_or_else(|| unreachable!());
+        Arc::make_mut(chunk).apply_unchecked(utils::block_coords(coords), action);
+        prev
+    }
+
+    #[test]
+    fn batched_explosion_light_matches_per_block_light() {
+        let (mut batched_chunks, mut batched) = lit_cave();
+        let (mut per_block_chunks, mut per_block) = lit_cave();
+
+        let targets = explosion().targets(|coords| batched_chunks.block(coords));
+        assert!(targets.len() > 100);
+
+        let hits = targets
+            .iter()
+            .map(|&(coords, action)| (coords, apply(&mut batched_chunks, coords, action), action))
+            .collect::<Vec<_>>();
+        batched.apply(&batched_chunks, hits);
+
+        for (coords, action) in targets {
+            let prev = apply(&mut per_block_chunks, coords, action);
+            per_block.apply(&per_block_chunks, [(coords, prev, action)]);
+        }
+
+        for coords in chunk_points().into_iter().flat_map(block_points) {
+            assert!(
+                batched.block_light(coords) == per_block.block_light(coords),
+                "light differs at {coords}",
+            );
+        }
+    }
+
+    #[test]
+    #[ignore = "timing budget, run with `cargo test --release -- --ignored`"]
+    fn batched_explosion_light_fits_in_a_tick() {
+        let (mut chunks, mut light) = lit_cave();
+        let hits = explosion()
+            .targets(|coords| chunks.block(coords))
+            .into_iter()
+            .map(|(coords, action)| (coords, apply(&mut chunks, coords, action), action))
+            .collect::<Vec<_>>();
+
+        let start = Instant::now();
+        light.apply(&chunks, hits);
+        let elapsed = start.elapsed();
+
+        let budget = event_loop::tick_duration();
+        assert!(
+            elapsed < budget,
+            "batched light update took {elapsed:?}, over the {budget:?} tick budget"
+        );
+    }
+}
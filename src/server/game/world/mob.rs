@@ -0,0 +1,108 @@
+use super::path::{PathSearch, PathState};
+use crate::server::SERVER_CONFIG;
+use nalgebra::{Point3, Vector3};
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Behaviour {
+    Wander,
+    Follow,
+}
+
+pub struct Brain {
+    behaviour: Behaviour,
+    path: VecDeque<Point3<i64>>,
+    cooldown: u64,
+    is_searching: bool,
+    rng: SmallRng,
+}
+
+impl Brain {
+    const REACH: f32 = 0.25;
+
+    pub fn new(behaviour: Behaviour, seed: u64) -> Self {
+        Self {
+            behaviour,
+            path: Default::default(),
+            cooldown: 0,
+            is_searching: false,
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn think(
+        &mut self,
+        feet: Point3<i64>,
+        target: Point3<f32>,
+        diagonal: Vector3<f32>,
+    ) -> Option<PathSearch> {
+        if self.is_searching {
+            return None;
+        }
+        if self.cooldown > 0 {
+            self.cooldown -= 1;
+            return None;
+        }
+
+        let MobConfig {
+            wander_radius,
+            wander_interval,
+            follow_distance,
+            repath_interval,
+            ..
+        } = SERVER_CONFIG.mob;
+
+        let (goal, tolerance) = match self.behaviour {
+            Behaviour::Wander => {
+                self.cooldown = wander_interval;
+                let delta = Vector3::new(
+                    self.rng.random_range(-wander_radius..=wander_radius),
+                    0,
+                    self.rng.random_range(-wander_radius..=wander_radius),
+                );
+                (feet + delta, 0)
+            }
+            Behaviour::Follow => {
+                self.cooldown = repath_interval;
+                (target.map(|c| c.floor() as i64), follow_distance)
+            }
+        };
+
+        self.is_searching = true;
+        Some(PathSearch::new(feet, goal, diagonal, tolerance))
+    }
+
+    pub fn receive(&mut self, state: PathState) {
+        self.is_searching = false;
+        match state {
+            PathState::Found(path) => self.path = path.into(),
+            PathState::Failed => self.path.clear(),
+        }
+    }
+
+    pub fn steer(&mut self, origin: Point3<f32>, feet: Point3<i64>) -> Option<Point3<i64>> {
+        while let Some(&waypoint) = self.path.front() {
+            let delta = waypoint.cast::<f32>() + Vector3::new(0.5, 0.0, 0.5) - origin;
+            if delta.xz().norm() < Self::REACH && feet.y >= waypoint.y {
+                self.path.pop_front();
+            } else {
+                return Some(waypoint);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MobConfig {
+    pub size: Vector3<f32>,
+    pub speed: f32,
+    pub jump_speed: f32,
+    wander_radius: i64,
+    wander_interval: u64,
+    follow_distance: i64,
+    repath_interval: u64,
+}
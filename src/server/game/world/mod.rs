@@ -1,10 +1,26 @@
 pub mod action;
 pub mod block;
+pub mod block_entity;
+pub mod border;
 pub mod chunk;
+pub mod edit;
+pub mod entity;
+pub mod explosion;
+pub mod flow;
 pub mod height;
+pub mod history;
 pub mod light;
-
-use super::player::{Player, WorldArea};
+pub mod mob;
+pub mod path;
+pub mod power;
+pub mod save;
+pub mod tick;
+
+use super::{
+    GameEvent,
+    inventory::Stack,
+    player::{Player, PlayerId, WorldArea},
+};
 use crate::{
     client::{ClientEvent, game::world::BlockVertex},
     server::{
@@ -23,14 +39,23 @@ use block::{
     Block, BlockLight,
     area::{BlockArea, BlockAreaLight},
     data::{Corner, SIDE_DELTAS, SIDE_MASKS, Side},
+    interaction::UseAction,
+    random::RandomTick,
+    reaction::Reaction,
 };
+use block_entity::{BlockEntity, BlockEntityStore};
+use border::WorldBorder;
 use chunk::{
     Chunk, ChunkDataStore,
     area::{ChunkArea, ChunkAreaLight},
     generator::ChunkGenerator,
 };
-use crossbeam_channel::SendError;
+use crossbeam_channel::{SendError, Sender};
+use edit::{Clipboard, Region, RegionEdit};
+use entity::{EntityKind, EntityStore};
+use explosion::Explosion;
 use height::HeightMap;
+use history::{BlockChange, Edit, EditHistory, StackChange};
 use light::WorldLight;
 use nalgebra::{Point2, Point3, Vector3, point};
 use rayon::{
@@ -38,26 +63,68 @@ use rayon::{
     slice::ParallelSliceMut,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
+use save::Saved;
 use serde::{Deserialize, Serialize};
 use std::{
     array,
     collections::{VecDeque, hash_map::Entry},
     mem,
     ops::{Index, Range},
+    sync::Arc,
 };
+use tick::TickScheduler;
 
-#[derive(Default)]
 pub struct World {
+    profile: &'static WorldProfile,
+    game_tx: Sender<GameEvent>,
     chunks: ChunkStore,
     heights: HeightMap,
     generator: ChunkGenerator,
-    actions: ActionStore,
+    actions: Saved<ActionStore>,
     light: WorldLight,
+    ticks: Saved<TickScheduler>,
+    entities: EntityStore,
+    block_entities: Saved<BlockEntityStore>,
+    histories: FxHashMap<PlayerId, EditHistory>,
+    clipboard: Option<Clipboard>,
     hover: Option<BlockIntersection>,
+    is_in_portal: bool,
 }
 
 impl World {
-    pub const Y_RANGE: Range<i32> = -4..20;
+    pub fn new(profile: &'static WorldProfile, game_tx: Sender<GameEvent>) -> Self {
+        Self {
+            profile,
+            game_tx,
+            chunks: Default::default(),
+            heights: Default::default(),
+            generator: ChunkGenerator::new(profile.seed),
+            actions: ActionStore::load(&profile.save_dir),
+            light: Default::default(),
+            ticks: TickScheduler::load(&profile.save_dir),
+            entities: Default::default(),
+            block_entities: BlockEntityStore::load(&profile.save_dir),
+            histories: Default::default(),
+            clipboard: None,
+            hover: None,
+            is_in_portal: false,
+        }
+    }
+
+    fn give(&self, block: Option<Block>) {
+        if let Some(block) = block {
+            _ = self
+                .game_tx
+                .send(GameEvent::StackGiven(Stack::new(block, 1)));
+        }
+    }
+
+    fn is_portal(&self, origin: Point3<f32>) -> bool {
+        self.chunks
+            .block(origin.map(|c| c.floor() as i64))
+            .data()
+            .portal
+    }
 
     fn par_insert_many<P>(&mut self, points: P) -> Vec<Point3<i32>>
     where
@@ -65,12 +132,22 @@ impl World {
     {
         points
             .into_par_iter()
-            .filter(|coords| !self.chunks.0.contains_key(coords))
-            .filter_map(|coords| Some((coords, self.generate(coords)?)))
+            .filter(|&coords| {
+                !self.chunks.is_generated(coords)
+                    && SERVER_CONFIG.world.border.contains_chunk(coords.xz())
+            })
+            .map(|coords| (coords, self.generate(coords)))
             .into_seq_iter()
-            .map(|(coords, chunk)| {
-                self.chunks.0.insert(coords, chunk);
-                coords
+            .filter_map(|(coords, chunk)| {
+                self.block_entities
+                    .retain_matching(coords, chunk.as_deref());
+                if let Some(chunk) = chunk {
+                    self.chunks.chunks.insert(coords, chunk);
+                    Some(coords)
+                } else {
+                    self.chunks.empty.insert(coords);
+                    None
+                }
             })
             .collect()
     }
@@ -81,7 +158,6 @@ impl World {
         self.light.par_insert_many(&self.chunks, &self.heights, points)
     }
 
-    #[rustfmt::skip]
     fn apply(
         &mut self,
         coords: Point3<i64>,
@@ -90,16 +166,180 @@ impl World {
         server_tx: &ServerSender,
         area: WorldArea,
         ray: Ray,
-    ) {
+    ) -> Option<Vec<BlockChange>> {
         let mut branch = Branch::default();
         if !branch.apply(&self.chunks, coords, normal, action) {
+            return None;
+        }
+        let edit = branch.changes(&self.chunks);
+        self.commit(branch, server_tx, area, ray);
+        Some(edit)
+    }
+
+    fn record(&mut self, player: PlayerId, edit: Edit) {
+        self.histories.entry(player).or_default().record(edit);
+    }
+
+    fn revert(
+        &mut self,
+        player: PlayerId,
+        is_undo: bool,
+        paid: Option<Block>,
+        server_tx: &ServerSender,
+        area: WorldArea,
+        ray: Ray,
+    ) {
+        let cost = self.histories.get(&player).and_then(|history| {
+            if is_undo {
+                history.undo_cost()
+            } else {
+                history.redo_cost()
+            }
+        });
+        if cost != paid {
+            self.give(paid);
+            if let Some(block) = cost {
+                _ = self
+                    .game_tx
+                    .send(GameEvent::RevertRequested { block, is_undo });
+                return;
+            }
+        }
+
+        let edit = self.histories.get_mut(&player).and_then(|history| {
+            if is_undo {
+                history.undo()
+            } else {
+                history.redo()
+            }
+        });
+        let Some(Edit { changes, stack }) = edit else {
             return;
+        };
+
+        let mut branch = Branch::default();
+        let applied = branch.revert(&self.chunks, changes);
+        if !applied.is_empty() {
+            self.commit(branch, server_tx, area, ray);
+        }
+        if let Some(StackChange {
+            coords,
+            block,
+            is_taken,
+        }) = stack
+            && applied.contains(&coords) != is_taken
+        {
+            self.give(Some(block));
+        }
+    }
+
+    fn edit(
+        &mut self,
+        region: Region,
+        edit: RegionEdit,
+        player: PlayerId,
+        server_tx: &ServerSender,
+        area: WorldArea,
+        ray: Ray,
+    ) {
+        if !region.is_editable() {
+            return;
+        }
+
+        let blocks = match edit {
+            RegionEdit::Copy => {
+                self.clipboard = Some(Clipboard::copy(|coords| self.chunks.block(coords), region));
+                return;
+            }
+            RegionEdit::Paste { turns } => match &self.clipboard {
+                Some(clipboard) => clipboard.paste(region.min(), turns),
+                None => return,
+            },
+            _ => edit.targets(|coords| self.chunks.block(coords), region),
+        };
+
+        let mut branch = Branch::default();
+        if branch.set_many(&self.chunks, blocks) {
+            let edit = branch.changes(&self.chunks);
+            self.commit(branch, server_tx, area, ray);
+            self.record(player, Edit::new(edit, None));
         }
+    }
 
-        let (actions, mut inserts, mut removals) = branch.merge(&mut self.chunks);
+    fn explode(
+        &mut self,
+        explosion: Explosion,
+        player: PlayerId,
+        server_tx: &ServerSender,
+        area: WorldArea,
+        ray: Ray,
+    ) {
+        let mut branch = Branch::default();
+        if branch.explode(&self.chunks, explosion) {
+            let edit = branch.changes(&self.chunks);
+            self.commit(branch, server_tx, area, ray);
+            self.record(player, Edit::new(edit, None));
+        }
+    }
+
+    fn tick(&mut self, server_tx: &ServerSender, area: WorldArea, ray: Ray) {
+        let mut branch = Branch::default();
+        let mut networks = FxHashSet::default();
+        let mut actions = vec![];
+        let mut explosions = vec![];
+
+        for coords in self.ticks.advance(area) {
+            if let Some(power) = self.chunks.block(coords).data().explosion {
+                explosions.push(Explosion::new(coords.cast() + Vector3::repeat(0.5), power));
+                continue;
+            }
+            actions.extend(power::propagate(
+                |coords| self.chunks.block(coords),
+                coords,
+                &mut networks,
+            ));
+            if let Some(action) = branch.reaction(&self.chunks, coords) {
+                actions.push((coords, action));
+            }
+        }
+
+        actions.extend(
+            self.ticks
+                .random_ticks(&self.chunks, area)
+                .into_iter()
+                .filter_map(|(coords, side)| branch.random_tick(&self.chunks, coords, side)),
+        );
+
+        if !actions.is_empty() || !explosions.is_empty() {
+            branch.execute_actions(&self.chunks, actions);
+            for explosion in explosions {
+                branch.explode(&self.chunks, explosion);
+            }
+            self.commit(branch, server_tx, area, ray);
+        }
+
+        self.entities.tick(&self.chunks, area, ray.origin);
+        self.entities.sync(area, server_tx);
+
+        self.actions.autosave();
+        self.ticks.autosave();
+        self.block_entities.autosave();
+    }
+
+    #[rustfmt::skip]
+    fn commit(&mut self, branch: Branch, server_tx: &ServerSender, area: WorldArea, ray: Ray) {
+        let is_reverted = branch.is_reverted;
+        let (hits, mut inserts, mut removals) = branch.merge(&mut self.chunks);
+        if hits.is_empty() {
+            return;
+        }
 
         self.light.extend_placeholders(self.heights.load_placeholders(inserts.iter().copied()));
-        let light_updates = self.light.apply(&self.chunks, actions.iter().copied());
+        let light_updates = self.light.apply(&self.chunks, hits.iter().copied());
+        let actions = hits
+            .into_iter()
+            .map(|(coords, _, action)| (coords, action))
+            .collect::<Vec<_>>();
 
         inserts.retain(|&coords| area.client_contains(coords));
         removals.retain(|&coords| area.client_contains(coords));
@@ -118,9 +358,65 @@ impl World {
         _ = Self::send_unloads(removals, Some(group_id), server_tx);
         _ = self.send_loads(inserts, group_id, server_tx);
 
+        self.ticks.schedule_around(&self.chunks, actions.iter().map(|&(coords, _)| coords));
+        self.replace_block_entities(actions.iter().map(|&(coords, _)| coords), is_reverted, area, server_tx);
         self.actions.extend(actions);
     }
 
+    fn replace_block_entities<P>(
+        &mut self,
+        points: P,
+        is_reverted: bool,
+        area: WorldArea,
+        server_tx: &ServerSender,
+    ) where
+        P: IntoIterator<Item = Point3<i64>>,
+    {
+        for coords in points {
+            let Some(prev) = self
+                .block_entities
+                .replace(coords, self.chunks.block(coords))
+            else {
+                continue;
+            };
+            for block in prev.into_iter().flat_map(BlockEntity::drops) {
+                if is_reverted {
+                    self.give(Some(block));
+                } else {
+                    self.entities.spawn(
+                        EntityKind::Item(Stack::new(block, 1)),
+                        coords.cast() + Vector3::repeat(0.5),
+                        Vector3::y() * 0.2,
+                    );
+                }
+            }
+            if area.client_contains(utils::chunk_coords(coords)) {
+                self.send_block_entity(coords, server_tx);
+            }
+        }
+    }
+
+    fn send_block_entity(&self, coords: Point3<i64>, server_tx: &ServerSender) {
+        _ = server_tx.send(ServerEvent::BlockEntityUpdated {
+            coords,
+            entity: self.block_entities.get(coords).cloned(),
+        });
+    }
+
+    fn send_block_entities(
+        &self,
+        coords: Point3<i32>,
+        server_tx: &ServerSender,
+    ) -> Result<(), SendError<ServerEvent>> {
+        self.block_entities
+            .chunk(coords)
+            .map(|(coords, entity)| ServerEvent::BlockEntityUpdated {
+                coords,
+                entity: Some(entity.clone()),
+            })
+            .try_for_each(|event| server_tx.send(event))
+    }
+
     fn updates(
         &self,
         inserts: impl IntoIterator<Item = Point3<i32>>,
@@ -133,7 +429,7 @@ impl World {
             .chain(Self::block_area_points(block_updates).map(utils::chunk_coords))
             .filter(|coords| {
                 area.client_contains(*coords)
-                    && self.chunks.0.contains_key(coords)
+                    && self.chunks.contains(*coords)
                     && !loads.contains(coords)
                     && !unloads.contains(coords)
             })
@@ -148,12 +444,18 @@ impl World {
     ) -> Result<(), SendError<ServerEvent>> {
         points
             .into_iter()
-            .map(|coords| ServerEvent::ChunkLoaded {
-                coords,
-                data: ChunkData::new(&self.chunks, &self.light, coords).into(),
-                group_id: Some(group_id),
+            .map(|coords| {
+                let event = ServerEvent::ChunkLoaded {
+                    coords,
+                    data: ChunkData::new(&self.chunks, &self.light, coords).into(),
+                    group_id: Some(group_id),
+                };
+                (coords, event)
+            })
+            .try_for_each(|(coords, event)| {
+                server_tx.send(event)?;
+                self.send_block_entities(coords, server_tx)
             })
-            .try_for_each(|event| server_tx.send(event))
     }
 
     fn par_send_loads<P: IntoParallelIterator<Item = Point3<i32>>>(
@@ -163,13 +465,19 @@ impl World {
     ) -> Result<(), SendError<ServerEvent>> {
         points
             .into_par_iter()
-            .map(|coords| ServerEvent::ChunkLoaded {
-                coords,
-                data: ChunkData::new(&self.chunks, &self.light, coords).into(),
-                group_id: None,
+            .map(|coords| {
+                let event = ServerEvent::ChunkLoaded {
+                    coords,
+                    data: ChunkData::new(&self.chunks, &self.light, coords).into(),
+                    group_id: None,
+                };
+                (coords, event)
             })
             .into_seq_iter()
-            .try_for_each(|event| server_tx.send(event))
+            .try_for_each(|(coords, event)| {
+                server_tx.send(event)?;
+                self.send_block_entities(coords, server_tx)
+            })
     }
 
     fn send_updates<P: IntoIterator<Item = Point3<i32>>>(
@@ -204,16 +512,12 @@ impl World {
             .try_for_each(|event| server_tx.send(event))
     }
 
-    fn generate(&self, coords: Point3<i32>) -> Option<Box<Chunk>> {
-        if self.chunks.0.contains_key(&coords) {
-            None
-        } else {
-            let mut chunk = Box::new(self.generator.generate(coords));
-            for (coords, action) in self.actions.chunk_actions(coords) {
-                chunk.apply_unchecked(coords, action);
-            }
-            (!chunk.is_empty()).then_some(chunk)
+    fn generate(&self, coords: Point3<i32>) -> Option<Arc<Chunk>> {
+        let mut chunk = self.generator.generate(coords);
+        for (coords, action) in self.actions.chunk_actions(coords) {
+            chunk.apply_unchecked(coords, action);
         }
+        (!chunk.is_empty()).then(|| Arc::new(chunk))
     }
 
     fn send_unloads<P: IntoIterator<Item = Point3<i32>>>(
@@ -252,33 +556,46 @@ impl EventHandler<WorldEvent> for World {
     fn handle(&mut self, event: &WorldEvent, server_tx: Self::Context<'_>) {
         match *event {
             WorldEvent::PlayerConnected { area, ray } => {
+                _ = server_tx.send(ServerEvent::WorldInitialized {
+                    y_range: SERVER_CONFIG.world.y_range(),
+                    border: SERVER_CONFIG.world.border,
+                    sky: self.profile.sky.clone(),
+                });
+
                 let inserts = self.par_insert_many(area.par_server_points());
 
                 self.par_light_up(&inserts);
+                self.ticks.resume(area);
 
                 let mut loads = area
                     .client_points()
-                    .filter(|&coords| self.chunks.0.contains_key(&coords))
+                    .filter(|&coords| self.chunks.contains(coords))
                     .collect::<Vec<_>>();
 
                 loads.par_sort_unstable_by_key(|&coords| {
                     utils::magnitude_squared(coords, utils::chunk_coords(ray.origin))
                 });
 
+                self.hover = None;
+                self.is_in_portal = self.is_portal(ray.origin);
                 self.handle(&WorldEvent::BlockHoverRequested { ray }, server_tx);
 
                 _ = self.par_send_loads(loads, server_tx);
+
+                self.entities.reset_visible();
+                self.entities.sync(area, server_tx);
             }
             WorldEvent::WorldAreaChanged { prev, cur, ray } => {
                 let inserts = self.par_insert_many(cur.par_exclusive_server_points(prev));
                 let block_updates = self.par_light_up(&inserts);
+                self.ticks.resume(cur);
                 let loads = cur
                     .exclusive_client_points(prev)
-                    .filter(|&coords| self.chunks.0.contains_key(&coords))
+                    .filter(|&coords| self.chunks.contains(coords))
                     .collect();
                 let unloads = prev
                     .exclusive_client_points(cur)
-                    .filter(|&coords| self.chunks.0.contains_key(&coords))
+                    .filter(|&coords| self.chunks.contains(coords))
                     .collect();
                 let updates = self.updates(inserts, block_updates, cur, &loads, &unloads);
 
@@ -287,8 +604,24 @@ impl EventHandler<WorldEvent> for World {
                 _ = Self::send_unloads(unloads, None, server_tx);
                 _ = self.par_send_loads(loads, server_tx);
                 _ = self.par_send_updates(updates, server_tx);
+
+                self.entities.sync(cur, server_tx);
+            }
+            WorldEvent::PlayerLeft { player } => {
+                self.histories.remove(&player);
+                self.actions.store();
+                self.ticks.store();
+                self.block_entities.store();
             }
             WorldEvent::BlockHoverRequested { ray } => {
+                let is_in_portal = self.is_portal(ray.origin);
+                if !mem::replace(&mut self.is_in_portal, is_in_portal)
+                    && is_in_portal
+                    && let Some(portal) = &self.profile.portal
+                {
+                    _ = self.game_tx.send(GameEvent::TravelRequested(portal));
+                }
+
                 let hover = ray.cast(SERVER_CONFIG.player.reach).find(
                     |&BlockIntersection { coords, .. }| {
                         self.chunks
@@ -311,31 +644,162 @@ impl EventHandler<WorldEvent> for World {
                     )));
                 }
             }
-            WorldEvent::BlockPlaced { block, area, ray } => {
-                if let Some(BlockIntersection { coords, normal }) = self.hover {
-                    self.apply(
+            WorldEvent::BlockPlaced {
+                block,
+                player,
+                area,
+                ray,
+            } => {
+                let Some(BlockIntersection { coords, normal }) = self.hover else {
+                    self.give(Some(block));
+                    return;
+                };
+                let y_range = SERVER_CONFIG.world.block_y_range();
+                if y_range.contains(&(coords + normal).y) {
+                    if let Some(edit) = self.apply(
                         coords + normal,
                         normal,
-                        BlockAction::Place(block),
+                        BlockAction::Place(block.oriented(normal, ray.dir)),
                         server_tx,
                         area,
                         ray,
-                    );
+                    ) {
+                        let stack = StackChange {
+                            coords: coords + normal,
+                            block,
+                            is_taken: true,
+                        };
+                        self.record(player, Edit::new(edit, Some(stack)));
+                    } else {
+                        self.give(Some(block));
+                    }
+                } else {
+                    self.give(Some(block));
+                    _ = server_tx.send(ServerEvent::CommandFeedback {
+                        message: format!(
+                            "blocks can only be placed between y = {} and y = {}",
+                            y_range.start,
+                            y_range.end - 1,
+                        ),
+                        is_error: true,
+                    });
+                }
+            }
+            WorldEvent::BlockUsed { held, area, ray } => {
+                let Some(BlockIntersection { coords, .. }) = self.hover else {
+                    self.give(held);
+                    return;
+                };
+                let block = self.chunks.block(coords);
+                if let Some(block) = block.used() {
+                    self.give(held);
+                    let mut branch = Branch::default();
+                    branch.execute_actions(&self.chunks, [(coords, BlockAction::Place(block))]);
+                    self.commit(branch, server_tx, area, ray);
+                } else if let Some(UseAction::Open(gui)) = block.data().on_use {
+                    self.give(held);
+                    _ = server_tx.send(ServerEvent::GuiOpened(gui));
+                } else if let Some(entity) = self.block_entities.get_mut(coords) {
+                    let given = entity.interact(held);
+                    self.give(given);
+                    self.send_block_entity(coords, server_tx);
+                } else {
+                    self.give(held);
                 }
             }
-            WorldEvent::BlockDestroyed { area, ray } => {
+            WorldEvent::SignWritten { ref text } => {
+                if let Some(BlockIntersection { coords, .. }) = self.hover
+                    && let Some(BlockEntity::Sign(sign)) = self.block_entities.get_mut(coords)
+                {
+                    sign.clone_from(text);
+                    self.send_block_entity(coords, server_tx);
+                } else {
+                    _ = server_tx.send(ServerEvent::CommandFeedback {
+                        message: "not looking at a sign".into(),
+                        is_error: true,
+                    });
+                }
+            }
+            WorldEvent::BlockDestroyed { player, area, ray } => {
                 if let Some(BlockIntersection { coords, normal }) = self.hover {
-                    self.apply(coords, normal, BlockAction::Destroy, server_tx, area, ray);
+                    let block = self.chunks.block(coords);
+                    let action = BlockAction::Destroy;
+                    if let Some(edit) = self.apply(coords, normal, action, server_tx, area, ray) {
+                        let mut stack = None;
+                        if block.fluid().is_none() {
+                            self.entities.spawn(
+                                EntityKind::Item(Stack::new(block.base(), 1)),
+                                coords.cast() + Vector3::repeat(0.5),
+                                Vector3::y() * 0.2,
+                            );
+                            stack = Some(StackChange {
+                                coords,
+                                block: block.base(),
+                                is_taken: false,
+                            });
+                        }
+                        self.record(player, Edit::new(edit, stack));
+                    }
                 }
             }
+            WorldEvent::RegionEdited {
+                region,
+                edit,
+                player,
+                area,
+                ray,
+            } => {
+                self.edit(region, edit, player, server_tx, area, ray);
+            }
+            WorldEvent::ExplosionTriggered {
+                explosion,
+                player,
+                area,
+                ray,
+            } => {
+                self.explode(explosion, player, server_tx, area, ray);
+            }
+            WorldEvent::EditUndone {
+                player,
+                paid,
+                area,
+                ray,
+            } => {
+                self.revert(player, true, paid, server_tx, area, ray);
+            }
+            WorldEvent::EditRedone {
+                player,
+                paid,
+                area,
+                ray,
+            } => {
+                self.revert(player, false, paid, server_tx, area, ray);
+            }
+            WorldEvent::EntitySummoned { kind, origin } => {
+                self.entities.spawn(kind, origin, Vector3::zeros());
+            }
+            WorldEvent::Tick { area, ray } => {
+                self.tick(server_tx, area, ray);
+            }
         }
     }
 }
 
 #[derive(Default)]
-pub struct ChunkStore(FxHashMap<Point3<i32>, Box<Chunk>>);
+pub struct ChunkStore {
+    chunks: FxHashMap<Point3<i32>, Arc<Chunk>>,
+    empty: FxHashSet<Point3<i32>>,
+}
 
 impl ChunkStore {
+    fn contains(&self, coords: Point3<i32>) -> bool {
+        self.chunks.contains_key(&coords)
+    }
+
+    fn is_generated(&self, coords: Point3<i32>) -> bool {
+        self.contains(coords) || self.empty.contains(&coords)
+    }
+
     fn chunk_area(&self, coords: Point3<i32>) -> ChunkArea {
         let mut value = ChunkArea::default();
         for delta in ChunkArea::chunk_deltas() {
@@ -358,7 +822,25 @@ impl ChunkStore {
     }
 
     fn get(&self, coords: Point3<i32>) -> Option<&Chunk> {
-        self.0.get(&coords).map(|v| &**v)
+        self.chunks.get(&coords).map(|v| &**v)
+    }
+
+    fn snapshot(&self, a: Point3<i64>, b: Point3<i64>) -> Self {
+        let padding = Vector3::new(1, 0, 1);
+        let min = utils::chunk_coords(a.inf(&b)) - padding;
+        let max = utils::chunk_coords(a.sup(&b)) + padding;
+        Self {
+            chunks: (min.x..=max.x)
+                .flat_map(|x| {
+                    SERVER_CONFIG
+                        .world
+                        .y_range()
+                        .flat_map(move |y| (min.z..=max.z).map(move |z| point![x, y, z]))
+                })
+                .filter_map(|coords| Some((coords, self.chunks.get(&coords)?.clone())))
+                .collect(),
+            empty: Default::default(),
+        }
     }
 }
 
@@ -366,17 +848,18 @@ impl Index<Point3<i32>> for ChunkStore {
     type Output = Chunk;
 
     fn index(&self, coords: Point3<i32>) -> &Self::Output {
-        &self.0[&coords]
+        &self.chunks[&coords]
     }
 }
 
 #[derive(Default)]
 struct Branch {
     actions: ActionStore,
+    is_reverted: bool,
 }
 
 type Changes = (
-    Vec<(Point3<i64>, BlockAction)>,
+    Vec<(Point3<i64>, Block, BlockAction)>,
     FxHashSet<Point3<i32>>,
     FxHashSet<Point3<i32>>,
 );
@@ -392,29 +875,96 @@ impl Branch {
         if !self.is_action_valid(chunks, coords, normal, action) {
             false
         } else {
-            self.execute_actions(chunks, VecDeque::from([(coords, action)]));
+            self.execute_actions(chunks, [(coords, action)]);
             true
         }
     }
 
+    fn revert(&mut self, chunks: &ChunkStore, mut changes: Vec<BlockChange>) -> Vec<Point3<i64>> {
+        changes.sort_by_key(|change| change.coords.y);
+        self.is_reverted = true;
+
+        let mut applied = vec![];
+        for BlockChange { coords, prev, cur } in changes {
+            if self.block(chunks, coords) == prev && self.set(chunks, coords, cur) {
+                applied.push(coords);
+            }
+        }
+        applied
+    }
+
+    fn set_many<B>(&mut self, chunks: &ChunkStore, blocks: B) -> bool
+    where
+        B: IntoIterator<Item = (Point3<i64>, Block)>,
+    {
+        let mut is_applied = false;
+        for (coords, block) in blocks {
+            is_applied |= self.set(chunks, coords, block);
+        }
+        is_applied
+    }
+
+    fn set(&mut self, chunks: &ChunkStore, coords: Point3<i64>, block: Block) -> bool {
+        let cur = self.block(chunks, coords);
+        let actions = if block == cur {
+            vec![]
+        } else if block == Block::AIR {
+            vec![BlockAction::Destroy]
+        } else if cur.is_replaceable() {
+            vec![BlockAction::Place(block)]
+        } else {
+            vec![BlockAction::Destroy, BlockAction::Place(block)]
+        };
+
+        let mut is_applied = false;
+        for action in actions {
+            if self.is_action_valid(chunks, coords, Vector3::y(), action) {
+                self.execute_actions(chunks, [(coords, action)]);
+                is_applied = true;
+            }
+        }
+        is_applied
+    }
+
+    fn changes(&self, chunks: &ChunkStore) -> Vec<BlockChange> {
+        self.actions
+            .0
+            .iter()
+            .flat_map(|(&chunk_coords, actions)| {
+                actions
+                    .keys()
+                    .map(move |&block_coords| utils::coords((chunk_coords, block_coords)))
+            })
+            .filter_map(|coords| {
+                let prev = chunks.block(coords);
+                let cur = self.block(chunks, coords);
+                (prev != cur).then_some(BlockChange { coords, prev, cur })
+            })
+            .collect()
+    }
+
     fn merge(self, chunks: &mut ChunkStore) -> Changes {
         let mut hits = vec![];
         let mut inserts = FxHashSet::default();
         let mut removals = FxHashSet::default();
 
         for (chunk_coords, actions) in self.actions.0 {
-            match chunks.0.entry(chunk_coords) {
+            match chunks.chunks.entry(chunk_coords) {
                 Entry::Occupied(mut entry) => {
-                    let chunk = entry.get_mut();
+                    let chunk = Arc::make_mut(entry.get_mut());
 
                     for (block_coords, action) in actions {
-                        if chunk.apply(block_coords, action) {
-                            hits.push((utils::coords((chunk_coords, block_coords)), action));
+                        let prev = chunk[block_coords];
+                        chunk.apply_unchecked(block_coords, action);
+                        let cur = chunk[block_coords];
+                        if cur != prev {
+                            hits.push((utils::coords((chunk_coords, block_coords)), prev, action));
                         }
                     }
 
                     if chunk.is_empty() {
                         entry.remove();
+                        chunks.empty.insert(chunk_coords);
                         removals.insert(chunk_coords);
                     }
                 }
@@ -425,10 +975,15 @@ impl Branch {
                         .peekable();
 
                     if actions.peek().is_some() {
-                        let chunk = entry.insert(Default::default());
+                        chunks.empty.remove(&chunk_coords);
+                        let chunk = Arc::make_mut(entry.insert(Default::default()));
                         for (block_coords, action) in actions {
                             chunk.apply_unchecked(block_coords, action);
-                            hits.push((utils::coords((chunk_coords, block_coords)), action));
+                            hits.push((
+                                utils::coords((chunk_coords, block_coords)),
+                                Block::AIR,
+                                action,
+                            ));
                         }
                         inserts.insert(chunk_coords);
                     }
@@ -439,6 +994,19 @@ impl Branch {
         (hits, inserts, removals)
     }
 
+    fn explode(&mut self, chunks: &ChunkStore, explosion: Explosion) -> bool {
+        let actions = explosion
+            .targets(|coords| self.block(chunks, coords))
+            .into_iter()
+            .filter(|&(coords, _)| {
+                self.is_action_valid(chunks, coords, Vector3::y(), BlockAction::Destroy)
+            })
+            .collect::<Vec<_>>();
+        let is_applied = !actions.is_empty();
+        self.execute_actions(chunks, actions);
+        is_applied
+    }
+
     fn is_action_valid(
         &self,
         chunks: &ChunkStore,
@@ -446,7 +1014,9 @@ impl Branch {
         normal: Vector3<i64>,
         action: BlockAction,
     ) -> bool {
-        if !World::Y_RANGE.contains(&utils::chunk_coords(coords).y)
+        if !SERVER_CONFIG.world.block_y_range().contains(&coords.y)
+            || !SERVER_CONFIG.world.border.contains(coords.xz())
+            || !chunks.is_generated(utils::chunk_coords(coords))
             || !self.block(chunks, coords).is_action_valid(action)
         {
             return false;
@@ -454,7 +1024,7 @@ impl Branch {
 
         if let BlockAction::Place(block) = action
             && let Some(surface) = block.data().valid_surface
-            && (normal != Vector3::y() || self.block(chunks, coords - normal) != surface)
+            && (normal != Vector3::y() || self.block(chunks, coords - normal).base() != surface)
         {
             return false;
         }
@@ -462,20 +1032,108 @@ impl Branch {
         true
     }
 
-    fn execute_actions(
+    fn execute_actions<A>(&mut self, chunks: &ChunkStore, actions: A)
+    where
+        A: IntoIterator<Item = (Point3<i64>, BlockAction)>,
+    {
+        let mut notified = VecDeque::new();
+
+        for (coords, action) in actions {
+            self.execute(chunks, coords, action, &mut notified);
+        }
+
+        while let Some(coords) = notified.pop_front() {
+            if let Some(action) = self.reaction(chunks, coords) {
+                self.execute(chunks, coords, action, &mut notified);
+            }
+        }
+    }
+
+    fn execute(
         &mut self,
         chunks: &ChunkStore,
-        mut actions: VecDeque<(Point3<i64>, BlockAction)>,
+        coords: Point3<i64>,
+        action: BlockAction,
+        notified: &mut VecDeque<Point3<i64>>,
     ) {
-        while let Some((coords, action)) = actions.pop_front() {
-            if action == BlockAction::Destroy {
-                let coords = coords + Vector3::y();
-                if self.block(chunks, coords).data().valid_surface.is_some() {
-                    actions.push_front((coords, BlockAction::Destroy));
+        match action {
+            BlockAction::Place(block) if block.data().falls() => {
+                let landing = self.landing(chunks, coords);
+                if landing != Some(coords) && self.block(chunks, coords) == block {
+                    self.insert(chunks, coords, BlockAction::Destroy, notified);
                 }
+                if let Some(coords) = landing {
+                    self.insert(chunks, coords, action, notified);
+                }
+            }
+            _ => self.insert(chunks, coords, action, notified),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        chunks: &ChunkStore,
+        coords: Point3<i64>,
+        action: BlockAction,
+        notified: &mut VecDeque<Point3<i64>>,
+    ) {
+        if !chunks.is_generated(utils::chunk_coords(coords)) {
+            return;
+        }
+        self.actions.insert(coords, action);
+        notified.extend(
+            tick::adjacent_points(coords)
+                .filter(|&coords| self.block(chunks, coords).data().update_delay == Some(0)),
+        );
+    }
+
+    fn reaction(&self, chunks: &ChunkStore, coords: Point3<i64>) -> Option<BlockAction> {
+        let block_at = |coords| self.block(chunks, coords);
+        let block = block_at(coords);
+        let data = block.data();
+        let below = block_at(coords - Vector3::y());
+
+        data.reactions.iter().find_map(|reaction| match reaction {
+            Reaction::Support => data
+                .valid_surface
+                .is_some_and(|surface| below.base() != surface)
+                .then_some(BlockAction::Destroy),
+            Reaction::Fall => below.is_replaceable().then_some(BlockAction::Place(block)),
+            Reaction::Power => power::react(block_at, coords),
+            Reaction::Flow => flow::flow(block_at, coords),
+        })
+    }
+
+    fn random_tick(
+        &self,
+        chunks: &ChunkStore,
+        coords: Point3<i64>,
+        side: Side,
+    ) -> Option<(Point3<i64>, BlockAction)> {
+        let block = self.block(chunks, coords);
+        match block.data().random_tick? {
+            RandomTick::Spread => {
+                let coords = coords + SIDE_DELTAS[side].cast();
+                let action = BlockAction::Place(block);
+                self.is_action_valid(chunks, coords, Vector3::y(), action)
+                    .then_some((coords, action))
+            }
+            RandomTick::Decay => Some((coords, BlockAction::Destroy)),
+            RandomTick::Become(block) => Some((coords, BlockAction::Place(block))),
+        }
+    }
+
+    fn landing(&self, chunks: &ChunkStore, mut coords: Point3<i64>) -> Option<Point3<i64>> {
+        let bottom = SERVER_CONFIG.world.block_y_range().start;
+        while chunks.is_generated(utils::chunk_coords(coords - Vector3::y()))
+            && self.block(chunks, coords - Vector3::y()).is_replaceable()
+        {
+            coords.y -= 1;
+            if coords.y < bottom {
+                return None;
             }
-            self.actions.insert(coords, action);
         }
+        Some(coords)
     }
 
     fn block(&self, chunks: &ChunkStore, coords: Point3<i64>) -> Block {
@@ -501,6 +1159,19 @@ impl ChunkData {
         }
     }
 
+    pub fn sky_floors(&self) -> [[u8; Chunk::DIM]; Chunk::DIM] {
+        array::from_fn(|x| {
+            array::from_fn(|z| {
+                (0..Chunk::DIM)
+                    .rev()
+                    .find(|&y| {
+                        !self.area_light[Vector3::new(x as i8, y as i8, z as i8)].is_sky_exposed()
+                    })
+                    .map_or(0, |y| y as u8 + 1)
+            })
+        })
+    }
+
     pub fn vertices(&self) -> (Vec<BlockVertex>, Vec<BlockVertex>) {
         let mut vertices = vec![];
         let mut transparent_vertices = vec![];
@@ -511,6 +1182,8 @@ impl ChunkData {
 
             if data.requires_blending {
                 transparent_vertices.extend(data.mesh(coords, &area, &area_light));
+            } else if data.is_fluid() {
+                vertices.extend(data.mesh(coords, &area, &area_light));
             } else {
                 let is_externally_lit = data.is_externally_lit();
                 vertices.extend(data.vertices(
@@ -642,7 +1315,7 @@ impl ChunkData {
 #[derive(Clone, Copy)]
 struct Quad {
     block: Block,
-    tex_index: u8,
+    tex_index: u16,
     corner_aos: EnumMap<Corner, u8>,
     corner_lights: EnumMap<Corner, BlockLight>,
 }
@@ -652,12 +1325,14 @@ impl Quad {
         let block = area.kernel();
         let data = block.data();
         let is_externally_lit = data.is_externally_lit();
-        (!data.requires_blending && area.is_side_visible(Some(side))).then(|| Self {
-            block,
-            tex_index: data.tex_index(),
-            corner_aos: area.corner_aos(Some(side), is_externally_lit),
-            corner_lights: area_light.corner_lights(Some(side), area),
-        })
+        (!data.requires_blending && !data.is_fluid() && area.is_side_visible(Some(side))).then(
+            || Self {
+                block,
+                tex_index: data.tex_index(Some(side)),
+                corner_aos: area.corner_aos(Some(side), is_externally_lit),
+                corner_lights: area_light.corner_lights(Some(side), area),
+            },
+        )
     }
 
     fn vertices(
@@ -688,15 +1363,19 @@ impl PartialEq for Quad {
 
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct BlockHoverData {
+    pub coords: Point3<i64>,
+    pub block: Block,
     pub hitbox: Aabb,
     pub brightness: BlockLight,
 }
 
 impl BlockHoverData {
     fn new(coords: Point3<i64>, area: &BlockArea, area_light: &BlockAreaLight) -> Self {
-        let data = area.kernel().data();
+        let block = area.kernel();
         Self {
-            hitbox: data.hitbox(coords),
+            coords,
+            block,
+            hitbox: block.data().hitbox(coords),
             brightness: Self::brightness(area, area_light),
         }
     }
@@ -714,6 +1393,9 @@ pub enum WorldEvent {
         area: WorldArea,
         ray: Ray,
     },
+    PlayerLeft {
+        player: PlayerId,
+    },
     WorldAreaChanged {
         prev: WorldArea,
         cur: WorldArea,
@@ -724,17 +1406,72 @@ pub enum WorldEvent {
     },
     BlockPlaced {
         block: Block,
+        player: PlayerId,
         area: WorldArea,
         ray: Ray,
     },
+    BlockUsed {
+        held: Option<Block>,
+        area: WorldArea,
+        ray: Ray,
+    },
+    SignWritten {
+        text: String,
+    },
     BlockDestroyed {
+        player: PlayerId,
+        area: WorldArea,
+        ray: Ray,
+    },
+    RegionEdited {
+        region: Region,
+        edit: RegionEdit,
+        player: PlayerId,
+        area: WorldArea,
+        ray: Ray,
+    },
+    ExplosionTriggered {
+        explosion: Explosion,
+        player: PlayerId,
+        area: WorldArea,
+        ray: Ray,
+    },
+    EditUndone {
+        player: PlayerId,
+        paid: Option<Block>,
+        area: WorldArea,
+        ray: Ray,
+    },
+    EditRedone {
+        player: PlayerId,
+        paid: Option<Block>,
+        area: WorldArea,
+        ray: Ray,
+    },
+    EntitySummoned {
+        kind: EntityKind,
+        origin: Point3<f32>,
+    },
+    Tick {
         area: WorldArea,
         ray: Ray,
     },
 }
 
 impl WorldEvent {
-    pub fn new(event: &Event, &Player { prev, cur, ray }: &Player) -> Option<Self> {
+    pub fn new(
+        event: &Event,
+        &Player {
+            id: player,
+            prev,
+            cur,
+            ray,
+            mode,
+            held,
+            ..
+        }: &Player,
+    ) -> Option<Self> {
+        let can_edit = mode.can_edit();
         match *event {
             Event::Client(ClientEvent::PlayerConnected { .. }) => {
                 Some(Self::PlayerConnected { area: cur, ray })
@@ -746,15 +1483,62 @@ impl WorldEvent {
                 ClientEvent::PlayerPositionChanged { .. }
                 | ClientEvent::PlayerOrientationChanged { .. },
             ) => Some(Self::BlockHoverRequested { ray }),
-            Event::Client(ClientEvent::BlockPlaced(block)) => Some(Self::BlockPlaced {
+            Event::Client(ClientEvent::BlockPlaced(_)) => held.map(|block| Self::BlockPlaced {
                 block,
+                player,
                 area: cur,
                 ray,
             }),
-            Event::Client(ClientEvent::BlockDestroyed) => {
-                Some(Self::BlockDestroyed { area: cur, ray })
-            }
+            Event::Client(ClientEvent::BlockUsed(_)) if can_edit => Some(Self::BlockUsed {
+                held,
+                area: cur,
+                ray,
+            }),
+            Event::Client(ClientEvent::BlockDestroyed) if can_edit => Some(Self::BlockDestroyed {
+                player,
+                area: cur,
+                ray,
+            }),
+            Event::Client(ClientEvent::EditUndone) if can_edit => Some(Self::EditUndone {
+                player,
+                paid: None,
+                area: cur,
+                ray,
+            }),
+            Event::Client(ClientEvent::EditRedone) if can_edit => Some(Self::EditRedone {
+                player,
+                paid: None,
+                area: cur,
+                ray,
+            }),
+            Event::Tick => Some(Self::Tick { area: cur, ray }),
+            Event::Disconnected => Some(Self::PlayerLeft { player }),
             _ => None,
         }
     }
 }
+
+#[derive(Deserialize)]
+pub struct WorldConfig {
+    y_range: Range<i32>,
+    pub border: WorldBorder,
+    pub spawn: String,
+}
+
+impl WorldConfig {
+    pub fn y_range(&self) -> Range<i32> {
+        self.y_range.clone()
+    }
+
+    pub fn block_y_range(&self) -> Range<i64> {
+        self.y_range.start as i64 * Chunk::DIM as i64..self.y_range.end as i64 * Chunk::DIM as i64
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WorldProfile {
+    pub seed: u32,
+    pub save_dir: String,
+    sky: String,
+    pub portal: Option<String>,
+}
@@ -0,0 +1,245 @@
+use super::block::{
+    Block,
+    data::{SIDE_DELTAS, Side},
+};
+use crate::{server::SERVER_CONFIG, shared::bound::Aabb};
+use nalgebra::{Point3, Vector3};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+pub struct PathSearch {
+    start: Point3<i64>,
+    goal: Point3<i64>,
+    width: f32,
+    height: i64,
+    tolerance: i64,
+    open: BinaryHeap<Node>,
+    costs: FxHashMap<Point3<i64>, u32>,
+    parents: FxHashMap<Point3<i64>, Point3<i64>>,
+    closest: (u32, Point3<i64>),
+    expanded: usize,
+}
+
+impl PathSearch {
+    const WALK_COST: u32 = 10;
+    const CLIMB_COST: u32 = 5;
+    const FALL_COST: u32 = 2;
+
+    pub fn new(
+        start: Point3<i64>,
+        goal: Point3<i64>,
+        diagonal: Vector3<f32>,
+        tolerance: i64,
+    ) -> Self {
+        let start_node = Node::new(start, 0, goal);
+        Self {
+            start,
+            goal,
+            width: diagonal.x.max(diagonal.z),
+            height: diagonal.y.ceil() as i64,
+            tolerance,
+            closest: (start_node.heuristic, start),
+            open: BinaryHeap::from([start_node]),
+            costs: FxHashMap::from_iter([(start, 0)]),
+            parents: Default::default(),
+            expanded: 0,
+        }
+    }
+
+    pub fn start(&self) -> Point3<i64> {
+        self.start
+    }
+
+    pub fn goal(&self) -> Point3<i64> {
+        self.goal
+    }
+
+    pub fn run<F>(mut self, block_at: F) -> PathState
+    where
+        F: Fn(Point3<i64>) -> Block + Copy,
+    {
+        while let Some(Node { coords, cost, .. }) = self.open.pop() {
+            if cost > self.costs[&coords] {
+                continue;
+            }
+
+            if self.is_goal(coords) {
+                return PathState::Found(self.path(coords));
+            }
+
+            self.expanded += 1;
+            if self.expanded > SERVER_CONFIG.path.max_nodes {
+                return self.partial();
+            }
+
+            for (next, step_cost) in self.moves(block_at, coords).collect::<Vec<_>>() {
+                let cost = cost + step_cost;
+                if self.costs.get(&next).is_none_or(|&value| cost < value) {
+                    let node = Node::new(next, cost, self.goal);
+                    if node.heuristic < self.closest.0 {
+                        self.closest = (node.heuristic, next);
+                    }
+                    self.costs.insert(next, cost);
+                    self.parents.insert(next, coords);
+                    self.open.push(node);
+                }
+            }
+        }
+        self.partial()
+    }
+
+    fn moves<F>(&self, block_at: F, coords: Point3<i64>) -> impl Iterator<Item = (Point3<i64>, u32)>
+    where
+        F: Fn(Point3<i64>) -> Block + Copy,
+    {
+        Side::HORIZONTAL.into_iter().filter_map(move |side| {
+            let next = coords + SIDE_DELTAS[side].cast();
+            if self.is_clear(block_at, next) {
+                self.fall(block_at, next)
+            } else {
+                self.climb(block_at, coords, next)
+            }
+        })
+    }
+
+    fn fall<F>(&self, block_at: F, coords: Point3<i64>) -> Option<(Point3<i64>, u32)>
+    where
+        F: Fn(Point3<i64>) -> Block + Copy,
+    {
+        (0..=SERVER_CONFIG.path.max_fall)
+            .map(|depth| (coords - Vector3::y() * depth, depth as u32))
+            .take_while(|&(coords, _)| self.is_clear(block_at, coords))
+            .find(|&(coords, _)| is_standable(block_at, coords))
+            .map(|(coords, depth)| (coords, Self::WALK_COST + Self::FALL_COST * depth))
+    }
+
+    fn climb<F>(
+        &self,
+        block_at: F,
+        coords: Point3<i64>,
+        next: Point3<i64>,
+    ) -> Option<(Point3<i64>, u32)>
+    where
+        F: Fn(Point3<i64>) -> Block + Copy,
+    {
+        let PathConfig { step_height, .. } = SERVER_CONFIG.path;
+        let floor = floor_height(block_at, coords)?;
+        (1..=step_height.ceil() as i64)
+            .take_while(|&dy| self.is_clear(block_at, coords + Vector3::y() * dy))
+            .map(|dy| (next + Vector3::y() * dy, dy as u32))
+            .find(|&(next, _)| self.is_clear(block_at, next) && is_standable(block_at, next))
+            .filter(|&(next, _)| {
+                floor_height(block_at, next).is_some_and(|height| height - floor <= step_height)
+            })
+            .map(|(next, dy)| (next, Self::WALK_COST + Self::CLIMB_COST * dy))
+    }
+
+    fn is_clear<F>(&self, block_at: F, coords: Point3<i64>) -> bool
+    where
+        F: Fn(Point3<i64>) -> Block,
+    {
+        (0..self.height).all(|dy| {
+            let coords = coords + Vector3::y() * dy;
+            is_passable(block_at(coords), coords, self.width)
+        })
+    }
+
+    fn is_goal(&self, coords: Point3<i64>) -> bool {
+        (coords - self.goal).abs().max() <= self.tolerance
+    }
+
+    fn partial(&self) -> PathState {
+        let (_, closest) = self.closest;
+        if closest == self.start {
+            PathState::Failed
+        } else {
+            PathState::Found(self.path(closest))
+        }
+    }
+
+    fn path(&self, mut coords: Point3<i64>) -> Vec<Point3<i64>> {
+        let mut path = vec![coords];
+        while let Some(&parent) = self.parents.get(&coords) {
+            path.push(parent);
+            coords = parent;
+        }
+        path.pop();
+        path.reverse();
+        path
+    }
+}
+
+pub enum PathState {
+    Found(Vec<Point3<i64>>),
+    Failed,
+}
+
+#[derive(PartialEq, Eq)]
+struct Node {
+    coords: Point3<i64>,
+    cost: u32,
+    heuristic: u32,
+}
+
+impl Node {
+    fn new(coords: Point3<i64>, cost: u32, goal: Point3<i64>) -> Self {
+        let delta = (goal - coords).abs();
+        Self {
+            coords,
+            cost,
+            heuristic: (delta.x + delta.z) as u32 * PathSearch::WALK_COST,
+        }
+    }
+
+    fn estimate(&self) -> u32 {
+        self.cost + self.heuristic
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate()
+            .cmp(&self.estimate())
+            .then(other.heuristic.cmp(&self.heuristic))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn is_passable(block: Block, coords: Point3<i64>, width: f32) -> bool {
+    let footprint = Aabb::new(
+        coords.cast() + Vector3::new(1.0 - width, 0.0, 1.0 - width) * 0.5,
+        Vector3::new(width, 1.0, width),
+    );
+    block.fluid().is_none()
+        && (block.is_replaceable() || !block.data().hitbox(coords).overlaps(footprint))
+}
+
+fn is_standable<F>(block_at: F, coords: Point3<i64>) -> bool
+where
+    F: Fn(Point3<i64>) -> Block,
+{
+    !block_at(coords - Vector3::y()).is_replaceable()
+}
+
+fn floor_height<F>(block_at: F, coords: Point3<i64>) -> Option<f32>
+where
+    F: Fn(Point3<i64>) -> Block,
+{
+    let coords = coords - Vector3::y();
+    let block = block_at(coords);
+    (!block.is_replaceable()).then(|| block.data().hitbox(coords).max().y)
+}
+
+#[derive(Deserialize)]
+pub struct PathConfig {
+    step_height: f32,
+    max_fall: i64,
+    max_nodes: usize,
+}
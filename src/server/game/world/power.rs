@@ -0,0 +1,224 @@
+use super::{
+    action::BlockAction,
+    block::{
+        Block,
+        power::{Power, PowerData},
+    },
+    tick,
+};
+use nalgebra::Point3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+
+pub fn propagate<F>(
+    block_at: F,
+    coords: Point3<i64>,
+    visited: &mut FxHashSet<Point3<i64>>,
+) -> Vec<(Point3<i64>, BlockAction)>
+where
+    F: Fn(Point3<i64>) -> Block,
+{
+    if !is_conductor(block_at(coords)) || !visited.insert(coords) {
+        return vec![];
+    }
+
+    let mut network = vec![coords];
+    let mut deq = VecDeque::from([coords]);
+    while let Some(coords) = deq.pop_front() {
+        for coords in tick::adjacent_points(coords) {
+            if is_conductor(block_at(coords)) && visited.insert(coords) {
+                network.push(coords);
+                deq.push_back(coords);
+            }
+        }
+    }
+
+    let mut levels = FxHashMap::default();
+    for &coords in &network {
+        let level = tick::adjacent_points(coords)
+            .filter_map(|coords| block_at(coords).power())
+            .filter(|power| matches!(power.data, PowerData::Emitter { .. }))
+            .map(|power| power.level)
+            .max()
+            .unwrap_or(0);
+        if level != 0 {
+            levels.insert(coords, level);
+            deq.push_back(coords);
+        }
+    }
+
+    while let Some(coords) = deq.pop_front() {
+        let level = levels[&coords] - 1;
+        if level == 0 {
+            continue;
+        }
+        for coords in tick::adjacent_points(coords) {
+            if is_conductor(block_at(coords))
+                && levels.get(&coords).is_none_or(|&value| value < level)
+            {
+                levels.insert(coords, level);
+                deq.push_back(coords);
+            }
+        }
+    }
+
+    network
+        .into_iter()
+        .filter_map(|coords| {
+            let block = block_at(coords);
+            let target = block.with_power_level(levels.get(&coords).copied().unwrap_or(0));
+            (target != block).then_some((coords, BlockAction::Place(target)))
+        })
+        .collect()
+}
+
+pub fn react<F>(block_at: F, coords: Point3<i64>) -> Option<BlockAction>
+where
+    F: Fn(Point3<i64>) -> Block,
+{
+    let block = block_at(coords);
+    let power = block.power()?;
+    let target = match power.data {
+        PowerData::Emitter { pulse: Some(_), .. } if power.is_powered() => {
+            block.with_power_level(0)
+        }
+        PowerData::Consumer => {
+            let is_powered = tick::adjacent_points(coords)
+                .filter_map(|coords| block_at(coords).power())
+                .any(|power| !matches!(power.data, PowerData::Consumer) && power.is_powered());
+            block.with_power_level(if is_powered { Power::MAX_LEVEL } else { 0 })
+        }
+        _ => block,
+    };
+    (target != block).then_some(BlockAction::Place(target))
+}
+
+fn is_conductor(block: Block) -> bool {
+    block
+        .power()
+        .is_some_and(|power| matches!(power.data, PowerData::Conductor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::game::world::block::data::STR_TO_BLOCK;
+
+    fn coords(x: i64) -> Point3<i64> {
+        Point3::new(x, 1, 0)
+    }
+
+    fn block_at(blocks: &FxHashMap<Point3<i64>, Block>) -> impl Fn(Point3<i64>) -> Block + Copy {
+        |coords| blocks.get(&coords).copied().unwrap_or(Block::AIR)
+    }
+
+    fn powered(name: &str) -> Block {
+        STR_TO_BLOCK[name].with_power_level(Power::MAX_LEVEL)
+    }
+
+    fn wire(level: u8) -> Block {
+        STR_TO_BLOCK["wire"].with_power_level(level)
+    }
+
+    fn apply(
+        mut blocks: FxHashMap<Point3<i64>, Block>,
+        actions: Vec<(Point3<i64>, BlockAction)>,
+    ) -> FxHashMap<Point3<i64>, Block> {
+        for (coords, action) in actions {
+            let BlockAction::Place(block) = action else {
+                panic!("propagation should only place blocks");
+            };
+            blocks.insert(coords, block);
+        }
+        blocks
+    }
+
+    fn levels(blocks: &FxHashMap<Point3<i64>, Block>, xs: impl Iterator<Item = i64>) -> Vec<u8> {
+        xs.map(|x| blocks[&coords(x)].power().unwrap().level)
+            .collect()
+    }
+
+    #[test]
+    fn level_decreases_along_the_wire() {
+        let mut blocks = FxHashMap::from_iter([(coords(0), powered("lever"))]);
+        blocks.extend((1..=3).map(|x| (coords(x), wire(0))));
+        let actions = propagate(block_at(&blocks), coords(2), &mut FxHashSet::default());
+        assert_eq!(levels(&apply(blocks, actions), 1..=3), [15, 14, 13]);
+    }
+
+    #[test]
+    fn level_runs_out_after_max_level_blocks() {
+        let mut blocks = FxHashMap::from_iter([(coords(0), powered("lever"))]);
+        blocks.extend((1..=16).map(|x| (coords(x), wire(0))));
+        let actions = propagate(block_at(&blocks), coords(1), &mut FxHashSet::default());
+        let blocks = apply(blocks, actions);
+        assert_eq!(levels(&blocks, 15..=16), [1, 0]);
+    }
+
+    #[test]
+    fn strongest_source_wins() {
+        let mut blocks =
+            FxHashMap::from_iter([(coords(0), powered("lever")), (coords(6), powered("lever"))]);
+        blocks.extend((1..=5).map(|x| (coords(x), wire(0))));
+        let actions = propagate(block_at(&blocks), coords(3), &mut FxHashSet::default());
+        assert_eq!(levels(&apply(blocks, actions), 1..=5), [15, 14, 13, 14, 15]);
+    }
+
+    #[test]
+    fn unpowered_network_drains() {
+        let mut blocks = FxHashMap::from_iter([(coords(0), STR_TO_BLOCK["lever"])]);
+        blocks.extend((1..=3).map(|x| (coords(x), wire(16 - x as u8))));
+        let actions = propagate(block_at(&blocks), coords(1), &mut FxHashSet::default());
+        assert_eq!(levels(&apply(blocks, actions), 1..=3), [0, 0, 0]);
+    }
+
+    #[test]
+    fn settled_network_is_left_alone() {
+        let mut blocks = FxHashMap::from_iter([(coords(0), powered("lever"))]);
+        blocks.extend((1..=3).map(|x| (coords(x), wire(16 - x as u8))));
+        assert!(propagate(block_at(&blocks), coords(1), &mut FxHashSet::default()).is_empty());
+    }
+
+    #[test]
+    fn network_is_visited_once() {
+        let mut blocks = FxHashMap::from_iter([(coords(0), powered("lever"))]);
+        blocks.extend((1..=3).map(|x| (coords(x), wire(0))));
+        let mut visited = FxHashSet::default();
+        assert_eq!(
+            propagate(block_at(&blocks), coords(1), &mut visited).len(),
+            3
+        );
+        assert!(propagate(block_at(&blocks), coords(3), &mut visited).is_empty());
+        assert!(propagate(block_at(&blocks), coords(0), &mut visited).is_empty());
+    }
+
+    #[test]
+    fn consumer_follows_its_neighbors() {
+        let lamp = STR_TO_BLOCK["lamp"];
+        let blocks = FxHashMap::from_iter([(coords(0), wire(3)), (coords(1), lamp)]);
+        assert!(
+            react(block_at(&blocks), coords(1))
+                == Some(BlockAction::Place(lamp.with_power_level(Power::MAX_LEVEL)))
+        );
+
+        let blocks = FxHashMap::from_iter([(coords(0), wire(0)), (coords(1), powered("lamp"))]);
+        assert!(react(block_at(&blocks), coords(1)) == Some(BlockAction::Place(lamp)));
+    }
+
+    #[test]
+    fn consumer_is_not_powered_by_another_consumer() {
+        let lamp = STR_TO_BLOCK["lamp"];
+        let blocks = FxHashMap::from_iter([(coords(0), powered("lamp")), (coords(1), lamp)]);
+        assert!(react(block_at(&blocks), coords(1)).is_none());
+    }
+
+    #[test]
+    fn pulse_emitter_turns_itself_off() {
+        let blocks = FxHashMap::from_iter([(coords(0), powered("button"))]);
+        assert!(
+            react(block_at(&blocks), coords(0)) == Some(BlockAction::Place(STR_TO_BLOCK["button"]))
+        );
+        let blocks = FxHashMap::from_iter([(coords(0), powered("lever"))]);
+        assert!(react(block_at(&blocks), coords(0)).is_none());
+    }
+}
@@ -0,0 +1,351 @@
+use super::block::{Block, data::STR_TO_BLOCK};
+use crate::{server::SERVER_CONFIG, shared::bincode};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    cell::RefCell,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, ErrorKind, Write},
+    num::NonZeroU64,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+};
+
+pub trait Save: Serialize + DeserializeOwned {
+    const NAME: &str;
+}
+
+pub struct Saved<T: Save> {
+    dir: &'static str,
+    tick: u64,
+    value: T,
+}
+
+impl<T: Save> Saved<T> {
+    pub fn load<F: FnOnce() -> T>(dir: &'static str, default: F) -> Self {
+        Self {
+            dir,
+            tick: 0,
+            value: load(dir, T::NAME).unwrap_or_else(default),
+        }
+    }
+
+    pub fn autosave(&mut self) {
+        self.tick += 1;
+        if self.tick.is_multiple_of(SERVER_CONFIG.save.interval.get()) {
+            self.store();
+        }
+    }
+
+    pub fn store(&self) {
+        if let Err(e) = store(self.dir, T::NAME, &self.value) {
+            eprintln!(
+                "[{}] store save FAILED: {e}",
+                path(self.dir, T::NAME).display()
+            );
+        }
+    }
+}
+
+impl<T: Save> Deref for Saved<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: Save> DerefMut for Saved<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T: Save> Drop for Saved<T> {
+    fn drop(&mut self) {
+        self.store();
+    }
+}
+
+fn load<T: DeserializeOwned>(dir: &str, name: &str) -> Option<T> {
+    let path = path(dir, name);
+    let deserialize = |file| {
+        let mut reader = BufReader::new(file);
+        let entries = bincode::deserialize_from::<Vec<PaletteEntry>, _>(&mut reader)?;
+        let palette = entries
+            .iter()
+            .map(PaletteEntry::block)
+            .collect::<Result<_, _>>()?;
+        PALETTE.set(Some(Palette::new(palette)));
+        let value = bincode::deserialize_from(reader);
+        PALETTE.take();
+        value
+    };
+    match File::open(&path) {
+        Ok(file) => Some(
+            deserialize(file)
+                .unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display())),
+        ),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => panic!("failed to read {}: {e}", path.display()),
+    }
+}
+
+fn store<T: Serialize>(dir: &str, name: &str, value: &T) -> io::Result<()> {
+    let path = path(dir, name);
+    let tmp_path = path.with_extension("tmp");
+
+    let mut bytes = vec![];
+    PALETTE.set(Some(Palette::default()));
+    let result = bincode::serialize_into(value, &mut bytes);
+    let entries = PALETTE
+        .take()
+        .unwrap_or_else(|| unreachable!())
+        .blocks
+        .into_iter()
+        .map(PaletteEntry::new)
+        .collect::<Vec<_>>();
+    result.map_err(io::Error::other)?;
+
+    fs::create_dir_all(path.parent().unwrap_or_else(|| unreachable!()))?;
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    bincode::serialize_into(entries, &mut writer).map_err(io::Error::other)?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    fs::rename(&tmp_path, &path)
+}
+
+fn path(dir: &str, name: &str) -> PathBuf {
+    [&*SERVER_CONFIG.save.dir, dir, &format!("{name}.bin")]
+        .iter()
+        .collect()
+}
+
+thread_local! {
+    static PALETTE: RefCell<Option<Palette>> = Default::default();
+}
+
+#[derive(Default)]
+struct Palette {
+    blocks: Vec<Block>,
+    indices: FxHashMap<Block, u32>,
+}
+
+impl Palette {
+    fn new(blocks: Vec<Block>) -> Self {
+        Self {
+            blocks,
+            indices: Default::default(),
+        }
+    }
+
+    fn index(&mut self, block: Block) -> u32 {
+        *self.indices.entry(block).or_insert_with(|| {
+            self.blocks.push(block);
+            self.blocks.len() as u32 - 1
+        })
+    }
+
+    fn block(&self, index: u32) -> Option<Block> {
+        self.blocks.get(index as usize).copied()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PaletteEntry {
+    name: String,
+    properties: Vec<(String, String)>,
+}
+
+impl PaletteEntry {
+    fn new(block: Block) -> Self {
+        Self {
+            name: block.name().into(),
+            properties: block
+                .properties()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    fn block(&self) -> Result<Block, bincode::DeserializeError> {
+        let block = STR_TO_BLOCK.get(&*self.name).copied().ok_or_else(|| {
+            bincode::DeserializeError::OtherString(format!("unknown block \"{}\"", self.name))
+        })?;
+        Ok(self.properties.iter().fold(block, |block, (name, value)| {
+            block.with_property(name, value).unwrap_or(block)
+        }))
+    }
+}
+
+pub mod palette {
+    use super::PALETTE;
+    use crate::server::game::world::block::Block;
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{self, Unexpected},
+    };
+
+    pub fn serialize<S: Serializer>(block: &Block, serializer: S) -> Result<S::Ok, S::Error> {
+        match PALETTE.with_borrow_mut(|palette| Some(palette.as_mut()?.index(*block))) {
+            Some(index) => index.serialize(serializer),
+            None => block.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Block, D::Error> {
+        if PALETTE.with_borrow(Option::is_none) {
+            return Block::deserialize(deserializer);
+        }
+        let index = u32::deserialize(deserializer)?;
+        PALETTE
+            .with_borrow(|palette| palette.as_ref()?.block(index))
+            .ok_or_else(|| {
+                de::Error::invalid_value(Unexpected::Unsigned(index as u64), &"a palette index")
+            })
+    }
+
+    pub mod seq {
+        use crate::server::game::world::block::Block;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Serialize, Deserialize)]
+        struct PaletteBlock(#[serde(with = "super")] Block);
+
+        pub fn serialize<S: Serializer>(
+            blocks: &[Block],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(blocks.iter().map(|&block| PaletteBlock(block)))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Block>, D::Error> {
+            let blocks = Vec::<PaletteBlock>::deserialize(deserializer)?;
+            Ok(blocks
+                .into_iter()
+                .map(|PaletteBlock(block)| block)
+                .collect())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SaveConfig {
+    dir: String,
+    interval: NonZeroU64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct Blocks {
+        #[serde(with = "palette")]
+        block: Block,
+        #[serde(with = "palette::seq")]
+        blocks: Vec<Block>,
+        count: u32,
+    }
+
+    impl Save for Blocks {
+        const NAME: &str = "blocks";
+    }
+
+    fn sample() -> Blocks {
+        let lever = STR_TO_BLOCK["lever"];
+        Blocks {
+            block: lever.with_property("powered", "true").unwrap(),
+            blocks: vec![lever, STR_TO_BLOCK["sand"], lever, Block::AIR, lever],
+            count: 7,
+        }
+    }
+
+    fn assert_sample(value: &Blocks) {
+        let sample = sample();
+        assert!(value.block == sample.block);
+        assert!(value.blocks == sample.blocks);
+        assert_eq!(value.count, sample.count);
+    }
+
+    fn with_dir(dir: &'static str, f: impl FnOnce()) {
+        let path = path(dir, "");
+        _ = fs::remove_dir_all(path.parent().unwrap());
+        f();
+        _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn value_round_trips_through_a_file() {
+        with_dir("test_round_trip", || {
+            store("test_round_trip", Blocks::NAME, &sample()).unwrap();
+            assert_sample(&load::<Blocks>("test_round_trip", Blocks::NAME).unwrap());
+        });
+    }
+
+    #[test]
+    fn palette_holds_each_block_once() {
+        with_dir("test_palette", || {
+            store("test_palette", Blocks::NAME, &sample()).unwrap();
+            let file = File::open(path("test_palette", Blocks::NAME)).unwrap();
+            let entries = bincode::deserialize_from::<Vec<PaletteEntry>, _>(file).unwrap();
+            let names = entries.iter().map(|entry| &*entry.name).collect::<Vec<_>>();
+            assert_eq!(names, ["lever", "lever", "sand", "air"]);
+            assert!(entries.iter().all(|entry| {
+                entry
+                    .block()
+                    .is_ok_and(|block| PaletteEntry::new(block).properties == entry.properties)
+            }));
+        });
+    }
+
+    #[test]
+    fn unknown_palette_block_is_an_error() {
+        let entry = PaletteEntry {
+            name: "missing".into(),
+            properties: vec![],
+        };
+        assert!(entry.block().is_err());
+    }
+
+    #[test]
+    fn unknown_palette_property_is_ignored() {
+        let entry = PaletteEntry {
+            name: "sand".into(),
+            properties: vec![("missing".into(), "true".into())],
+        };
+        assert!(
+            entry
+                .block()
+                .is_ok_and(|block| block == STR_TO_BLOCK["sand"])
+        );
+    }
+
+    #[test]
+    fn blocks_serialize_plainly_without_a_palette() {
+        let mut bytes = vec![];
+        bincode::serialize_into(sample(), &mut bytes).unwrap();
+        assert_sample(&bincode::deserialize_from(&*bytes).unwrap());
+    }
+
+    #[test]
+    fn missing_file_loads_the_default() {
+        with_dir("test_missing", || {
+            assert!(load::<Blocks>("test_missing", Blocks::NAME).is_none());
+            assert_eq!(
+                Saved::<Blocks>::load("test_missing", Default::default).count,
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn saved_value_is_stored_on_drop() {
+        with_dir("test_drop", || {
+            *Saved::load("test_drop", Blocks::default) = sample();
+            assert_sample(&Saved::load("test_drop", Blocks::default));
+        });
+    }
+}
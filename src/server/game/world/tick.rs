@@ -0,0 +1,158 @@
+use super::{
+    ChunkStore,
+    block::data::{SIDE_DELTAS, Side},
+    chunk::Chunk,
+    save::{Save, Saved},
+};
+use crate::{
+    server::{SERVER_CONFIG, game::player::WorldArea},
+    shared::utils::{self, ParallelIteratorExt},
+};
+use nalgebra::{Point3, Vector3};
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use rayon::iter::ParallelIterator;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct TickScheduler {
+    tick: u64,
+    pending: BTreeMap<u64, FxHashSet<Point3<i64>>>,
+    parked: FxHashMap<Point3<i32>, FxHashSet<Point3<i64>>>,
+}
+
+impl TickScheduler {
+    pub fn load(save_dir: &'static str) -> Saved<Self> {
+        Saved::load(save_dir, Default::default)
+    }
+
+    pub fn advance(&mut self, area: WorldArea) -> Vec<Point3<i64>> {
+        self.tick += 1;
+
+        let mut due = vec![];
+        while let Some(entry) = self.pending.first_entry()
+            && *entry.key() <= self.tick
+        {
+            for coords in entry.remove() {
+                let chunk_coords = utils::chunk_coords(coords);
+                if area.server_contains(chunk_coords) {
+                    due.push(coords);
+                } else {
+                    self.parked.entry(chunk_coords).or_default().insert(coords);
+                }
+            }
+        }
+        due
+    }
+
+    pub fn resume(&mut self, area: WorldArea) {
+        let parked = self
+            .parked
+            .extract_if(|&coords, _| area.server_contains(coords))
+            .flat_map(|(_, points)| points)
+            .collect::<Vec<_>>();
+        for coords in parked {
+            self.schedule(coords, 1);
+        }
+    }
+
+    pub fn random_ticks(&self, chunks: &ChunkStore, area: WorldArea) -> Vec<(Point3<i64>, Side)> {
+        area.par_server_points()
+            .filter(|&coords| chunks.contains(coords))
+            .flat_map_iter(|coords| {
+                let mut rng = SmallRng::seed_from_u64(self.seed(coords));
+                (0..SERVER_CONFIG.random_tick.speed).map(move |_| {
+                    let delta = Vector3::from_fn(|_, _| rng.random_range(..Chunk::DIM) as u8);
+                    let side = Side::HORIZONTAL[rng.random_range(..Side::HORIZONTAL.len())];
+                    (utils::coords((coords, Point3::from(delta))), side)
+                })
+            })
+            .into_seq_iter()
+            .collect()
+    }
+
+    pub fn schedule_around<P>(&mut self, chunks: &ChunkStore, points: P)
+    where
+        P: IntoIterator<Item = Point3<i64>>,
+    {
+        for coords in points {
+            for coords in adjacent_points(coords).chain([coords]) {
+                if let Some(delay) = Self::delay(chunks, coords)
+                    && delay != 0
+                {
+                    self.schedule(coords, delay);
+                }
+            }
+        }
+    }
+
+    pub fn schedule(&mut self, coords: Point3<i64>, delay: u64) {
+        self.pending
+            .entry(self.tick + delay)
+            .or_default()
+            .insert(coords);
+    }
+
+    fn seed(&self, coords: Point3<i32>) -> u64 {
+        let mut hasher = FxHasher::default();
+        (SERVER_CONFIG.random_tick.seed, self.tick, coords).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn delay(chunks: &ChunkStore, coords: Point3<i64>) -> Option<u64> {
+        let block = chunks.block(coords);
+        let inflow = block
+            .is_replaceable()
+            .then(|| {
+                adjacent_points(coords)
+                    .filter_map(|coords| chunks.block(coords).fluid())
+                    .map(|fluid| fluid.data.tick_delay.max(1))
+                    .min()
+            })
+            .flatten();
+        block.data().update_delay.into_iter().chain(inflow).min()
+    }
+}
+
+impl Save for TickScheduler {
+    const NAME: &str = "ticks";
+}
+
+pub fn adjacent_points(coords: Point3<i64>) -> impl Iterator<Item = Point3<i64>> {
+    SIDE_DELTAS.values().map(move |delta| coords + delta.cast())
+}
+
+#[derive(Deserialize)]
+pub struct RandomTickConfig {
+    speed: u32,
+    seed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_ticks_are_deterministic() {
+        let mut chunks = ChunkStore::default();
+        for y in SERVER_CONFIG.world.y_range() {
+            chunks
+                .chunks
+                .insert(Point3::new(0, y, 0), Default::default());
+        }
+        let area = WorldArea::default();
+        let [mut a, mut b] = [TickScheduler::default(), TickScheduler::default()];
+        for _ in 0..3 {
+            a.advance(area);
+            b.advance(area);
+            assert_eq!(a.random_ticks(&chunks, area), b.random_ticks(&chunks, area));
+        }
+        let prev = a.random_ticks(&chunks, area);
+        a.advance(area);
+        assert_ne!(a.random_ticks(&chunks, area), prev);
+    }
+}
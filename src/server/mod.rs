@@ -8,12 +8,31 @@ use event_loop::{EventLoop, EventLoopConfig};
 use game::{
     Game,
     clock::{ClockConfig, Time},
+    inventory::Inventory,
     player::PlayerConfig,
-    world::{BlockHoverData, ChunkData, block::Block},
+    weather::{WeatherConfig, WeatherState},
+    world::{
+        BlockHoverData, ChunkData, WorldConfig, WorldProfile,
+        block::interaction::GuiKind,
+        block_entity::{BlockEntity, BlockEntityConfig},
+        border::WorldBorder,
+        edit::EditConfig,
+        entity::{EntityConfig, EntityId, EntityKind},
+        explosion::ExplosionConfig,
+        history::HistoryConfig,
+        mob::MobConfig,
+        path::PathConfig,
+        save::SaveConfig,
+        tick::RandomTickConfig,
+    },
 };
 use nalgebra::{Point3, Vector3};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, LazyLock};
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    sync::{Arc, LazyLock},
+};
 use uuid::Uuid;
 use winit::event_loop::EventLoopProxy;
 
@@ -39,9 +58,15 @@ pub enum ServerEvent {
         origin: Point3<f32>,
         dir: Vector3<f32>,
         speed: f32,
-        inventory: Arc<[Block]>,
+        inventory: Inventory,
+    },
+    WorldInitialized {
+        y_range: Range<i32>,
+        border: WorldBorder,
+        sky: String,
     },
     TimeUpdated(Time),
+    WeatherUpdated(WeatherState),
     ChunkLoaded {
         coords: Point3<i32>,
         data: Arc<ChunkData>,
@@ -57,6 +82,31 @@ pub enum ServerEvent {
         group_id: Option<GroupId>,
     },
     BlockHovered(Option<BlockHoverData>),
+    BlockEntityUpdated {
+        coords: Point3<i64>,
+        entity: Option<BlockEntity>,
+    },
+    EntitySpawned {
+        id: EntityId,
+        kind: EntityKind,
+        origin: Point3<f32>,
+        velocity: Vector3<f32>,
+    },
+    EntityMoved {
+        id: EntityId,
+        origin: Point3<f32>,
+        velocity: Vector3<f32>,
+    },
+    EntityDespawned(EntityId),
+    PlayerTeleported {
+        origin: Point3<f32>,
+    },
+    InventoryUpdated(Inventory),
+    GuiOpened(GuiKind),
+    CommandFeedback {
+        message: String,
+        is_error: bool,
+    },
     #[serde(skip)]
     ClientDisconnected,
 }
@@ -70,7 +120,10 @@ impl ServerEvent {
         assert!(!self.is_special());
         !matches!(
             self,
-            Self::ChunkLoaded { .. } | Self::ChunkUnloaded { .. } | Self::ChunkUpdated { .. }
+            Self::ChunkLoaded { .. }
+                | Self::ChunkUnloaded { .. }
+                | Self::ChunkUpdated { .. }
+                | Self::BlockEntityUpdated { .. }
         )
     }
 }
@@ -131,6 +184,18 @@ struct ServerConfig {
     event_loop: EventLoopConfig,
     player: PlayerConfig,
     clock: ClockConfig,
+    save: SaveConfig,
+    random_tick: RandomTickConfig,
+    entity: EntityConfig,
+    path: PathConfig,
+    mob: MobConfig,
+    history: HistoryConfig,
+    edit: EditConfig,
+    explosion: ExplosionConfig,
+    block_entity: BlockEntityConfig,
+    world: WorldConfig,
+    worlds: BTreeMap<String, WorldProfile>,
+    weather: WeatherConfig,
 }
 
 static SERVER_CONFIG: LazyLock<ServerConfig> =
@@ -20,6 +20,18 @@ impl Aabb {
         }
     }
 
+    pub fn min(self) -> Point3<f32> {
+        self.min
+    }
+
+    pub fn max(self) -> Point3<f32> {
+        self.max
+    }
+
+    pub fn overlaps(self, other: Self) -> bool {
+        (0..3).all(|i| self.min[i] < other.max[i] && other.min[i] < self.max[i])
+    }
+
     pub fn pad(mut self, amount: f32) -> Self {
         self.min.apply(|c| *c -= amount);
         self.max.apply(|c| *c += amount);
@@ -268,13 +268,12 @@ pub unsafe trait Enum: Copy {
 
     fn to_index(self) -> usize;
 
-    #[define_opaque(Variants)]
     fn variants() -> Variants<Self> {
         (0..Self::LEN).map(|i| unsafe { Self::from_index_unchecked(i) })
     }
 }
 
-pub type Variants<E: Enum> = impl Iterator<Item = E>;
+pub type Variants<E> = std::iter::Map<std::ops::Range<usize>, fn(usize) -> E>;
 
 unsafe impl<E: Enum> Enum for Option<E>
 where